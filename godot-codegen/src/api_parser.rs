@@ -58,7 +58,7 @@ pub struct Class {
     pub is_instantiable: bool,
     pub inherits: Option<String>,
     // pub api_type: String,
-    // pub constants: Option<Vec<Constant>>,
+    pub constants: Option<Vec<ClassConstant>>,
     pub enums: Option<Vec<ClassEnum>>,
     pub methods: Option<Vec<Method>>,
     // pub properties: Option<Vec<Property>>,
@@ -93,6 +93,13 @@ pub struct Constant {
     pub value: i32,
 }
 
+// Class constants are not bound to i32, unlike enumerators
+#[derive(DeJson)]
+pub struct ClassConstant {
+    pub name: String,
+    pub value: i64,
+}
+
 #[derive(DeJson)]
 pub struct Property {
     #[nserde(rename = "type")]
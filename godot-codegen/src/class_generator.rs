@@ -6,8 +6,9 @@
 
 //! Generates a file for each Godot class
 
-use proc_macro2::{Literal, TokenStream};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::api_parser::*;
@@ -24,6 +25,13 @@ pub(crate) fn generate_class_files(
     let _ = std::fs::remove_dir_all(gen_path);
     std::fs::create_dir_all(gen_path).expect("create classes directory");
 
+    // Needed to collect inherited virtual methods
+    let classes_by_name: HashMap<&str, &Class> = api
+        .classes
+        .iter()
+        .map(|class| (class.name.as_str(), class))
+        .collect();
+
     let mut modules = vec![];
     for class in api.classes.iter() {
         #[cfg(feature = "minimal")]
@@ -35,7 +43,7 @@ pub(crate) fn generate_class_files(
             continue;
         }
 
-        let generated_class = make_class(class, ctx);
+        let generated_class = make_class(class, &classes_by_name, ctx);
         let file_contents = generated_class.tokens.to_string();
 
         let module_name = to_module_name(&class.name);
@@ -49,6 +57,8 @@ pub(crate) fn generate_class_files(
             class_ident,
             module_ident,
             inherits_macro_ident: generated_class.inherits_macro_ident,
+            virtual_trait_ident: generated_class.virtual_trait_ident,
+            notification_ident: generated_class.notification_ident,
             is_pub: generated_class.has_pub_module,
         });
     }
@@ -105,7 +115,11 @@ fn make_constructor(class: &Class, ctx: &Context, class_name_str: &Literal) -> T
     }
 }
 
-fn make_class(class: &Class, ctx: &mut Context) -> GeneratedClass {
+fn make_class(
+    class: &Class,
+    classes_by_name: &HashMap<&str, &Class>,
+    ctx: &mut Context,
+) -> GeneratedClass {
     //let sys = TokenStream::from_str("::godot_ffi");
     let base = match class.inherits.as_ref() {
         Some(base) => {
@@ -121,6 +135,10 @@ fn make_class(class: &Class, ctx: &mut Context) -> GeneratedClass {
     let constructor = make_constructor(class, ctx, &name_str);
 
     let methods = make_methods(&class.methods, &class.name, ctx);
+    let (notification_ident, notification_enum) =
+        make_notification_enum(class, classes_by_name, ctx);
+    let (virtual_trait_ident, virtual_trait) =
+        make_virtual_trait(class, &notification_ident, classes_by_name, ctx);
     let enums = make_enums(&class.enums, &class.name, ctx);
     let inherits_macro = format_ident!("inherits_transitive_{}", &class.name);
     let all_bases = ctx.inheritance_tree().map_all_bases(&class.name, ident);
@@ -151,7 +169,19 @@ fn make_class(class: &Class, ctx: &mut Context) -> GeneratedClass {
             impl #name {
                 #constructor
                 #methods
+
+                /// Sends the notification `what` to this object, calling `_notification()` from base classes to derived ones.
+                pub fn notify(&mut self, what: #notification_ident) {
+                    self.notification(i64::from(i32::from(what)), false);
+                }
+
+                /// Sends the notification `what` to this object, calling `_notification()` from derived classes to base ones.
+                pub fn notify_reversed(&mut self, what: #notification_ident) {
+                    self.notification(i64::from(i32::from(what)), true);
+                }
             }
+
+            #notification_enum
             impl crate::obj::GodotClass for #name {
                 type Base = #base;
                 type Declarer = crate::obj::dom::EngineDomain;
@@ -185,6 +215,8 @@ fn make_class(class: &Class, ctx: &mut Context) -> GeneratedClass {
                 }
             }
 
+            #virtual_trait
+
             #[macro_export]
             #[allow(non_snake_case)]
             macro_rules! #inherits_macro {
@@ -193,7 +225,15 @@ fn make_class(class: &Class, ctx: &mut Context) -> GeneratedClass {
                     #(
                         impl ::godot::obj::Inherits<::godot::engine::#all_bases> for $Class {}
                     )*
-                }
+                };
+
+                // Generic class: [impl generic params] [where clause]
+                ($Class:ty, [$($Params:tt)*] [$($Where:tt)*]) => {
+                    impl $($Params)* ::godot::obj::Inherits<::godot::engine::#name> for $Class $($Where)* {}
+                    #(
+                        impl $($Params)* ::godot::obj::Inherits<::godot::engine::#all_bases> for $Class $($Where)* {}
+                    )*
+                };
             }
         }
 
@@ -204,6 +244,8 @@ fn make_class(class: &Class, ctx: &mut Context) -> GeneratedClass {
     GeneratedClass {
         tokens,
         inherits_macro_ident: inherits_macro,
+        virtual_trait_ident,
+        notification_ident,
         has_pub_module: !enums.is_empty(),
     }
 }
@@ -238,9 +280,43 @@ fn make_module_file(classes_and_modules: Vec<GeneratedModule>) -> TokenStream {
         }
     });
 
+    let virtual_traits = classes_and_modules.iter().map(|m| {
+        let GeneratedModule {
+            module_ident,
+            virtual_trait_ident,
+            ..
+        } = m;
+
+        quote! {
+            pub use super::#module_ident::re_export::#virtual_trait_ident;
+        }
+    });
+
+    let notifications = classes_and_modules.iter().map(|m| {
+        let GeneratedModule {
+            module_ident,
+            notification_ident,
+            ..
+        } = m;
+
+        quote! {
+            pub use super::#module_ident::re_export::#notification_ident;
+        }
+    });
+
     quote! {
         #( #decls )*
 
+        /// Traits with the virtual methods of each engine class, to be implemented by user classes with `#[godot_api]`.
+        pub mod virtuals {
+            #( #virtual_traits )*
+        }
+
+        /// Notifications which can be received by each engine class, including the ones declared in its base classes.
+        pub mod notify {
+            #( #notifications )*
+        }
+
         #[doc(hidden)]
         pub mod class_macros {
             pub use crate::*;
@@ -264,6 +340,233 @@ fn make_methods(methods: &Option<Vec<Method>>, class_name: &str, ctx: &mut Conte
     }
 }
 
+/// Returns `class` followed by all its base classes, from derived to base.
+fn make_hierarchy<'a>(
+    class: &'a Class,
+    classes_by_name: &HashMap<&str, &'a Class>,
+    ctx: &mut Context,
+) -> Vec<&'a Class> {
+    let mut hierarchy = vec![class];
+    hierarchy.extend(
+        ctx.inheritance_tree()
+            .map_all_bases(&class.name, |base| classes_by_name.get(base).copied())
+            .into_iter()
+            .flatten(),
+    );
+    hierarchy
+}
+
+/// Generates `{Class}Notification`, containing the `NOTIFICATION_*` constants of `class` and all its base classes.
+fn make_notification_enum(
+    class: &Class,
+    classes_by_name: &HashMap<&str, &Class>,
+    ctx: &mut Context,
+) -> (Ident, TokenStream) {
+    let enum_name = format_ident!("{}Notification", class.name);
+
+    let mut seen = HashSet::new();
+    let mut enumerators = vec![];
+    let mut unique_ords = vec![];
+    for class_in_hierarchy in make_hierarchy(class, classes_by_name, ctx) {
+        let constants = class_in_hierarchy.constants.iter().flatten();
+
+        for constant in constants {
+            // NOTIFICATION_READY -> READY
+            let name = match constant.name.strip_prefix("NOTIFICATION_") {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if seen.insert(name) {
+                let name = ident(name);
+                let ordinal = Literal::i32_unsuffixed(constant.value as i32);
+
+                enumerators.push(quote! {
+                    pub const #name: Self = Self { ord: #ordinal };
+                });
+                unique_ords.push(constant.value as i32);
+            }
+        }
+    }
+
+    unique_ords.sort();
+    unique_ords.dedup();
+
+    let doc = format!(
+        "Notifications received by `{}`, including the ones declared in its base classes.\n\n\
+        Other values, e.g. custom notifications, can be constructed with `From<i32>`.",
+        class.name
+    );
+
+    // Unlike regular engine enums, every ordinal is valid: objects can receive notifications unknown to the engine
+    let tokens = quote! {
+        #[doc = #doc]
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+        pub struct #enum_name {
+            ord: i32
+        }
+        impl #enum_name {
+            #( #enumerators )*
+        }
+        impl crate::obj::EngineEnum for #enum_name {
+            fn try_from_ord(ord: i32) -> Option<Self> {
+                match ord {
+                    #( ord @ #unique_ords )|* => Some(Self { ord }),
+                    _ => None,
+                }
+            }
+            fn ord(self) -> i32 {
+                self.ord
+            }
+        }
+        impl From<i32> for #enum_name {
+            fn from(ord: i32) -> Self {
+                Self { ord }
+            }
+        }
+        impl From<#enum_name> for i32 {
+            fn from(what: #enum_name) -> i32 {
+                what.ord
+            }
+        }
+    };
+
+    (enum_name, tokens)
+}
+
+/// Generates `{Class}Virtual`, containing the virtual methods of `class` and all its base classes.
+fn make_virtual_trait(
+    class: &Class,
+    notification_ident: &Ident,
+    classes_by_name: &HashMap<&str, &Class>,
+    ctx: &mut Context,
+) -> (Ident, TokenStream) {
+    let class_name = ident(&class.name);
+    let trait_name = format_ident!("{}Virtual", class.name);
+
+    // Names of the trait's non-virtual methods; engine virtuals with the same name are not overridable
+    let mut seen: HashSet<String> = [
+        "init",
+        "to_string",
+        "register_class",
+        "on_notification",
+        "get_property",
+        "set_property",
+        "property_list",
+        "property_can_revert",
+        "property_get_revert",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    // Derived classes first, so that their declarations take precedence
+    let mut virtual_methods = vec![];
+    for class_in_hierarchy in make_hierarchy(class, classes_by_name, ctx) {
+        let methods = class_in_hierarchy.methods.iter().flatten();
+
+        for method in methods {
+            if !method.is_virtual || is_method_excluded(method, ctx) {
+                continue;
+            }
+
+            // _ready -> ready
+            let rust_name = method.name.strip_prefix('_').unwrap_or(&method.name);
+            if seen.insert(rust_name.to_string()) {
+                virtual_methods.push(make_virtual_method_declaration(method, rust_name, ctx));
+            }
+        }
+    }
+
+    let doc = format!(
+        "Virtual methods for user classes inheriting `{}`, including the ones declared in its base classes.\n\n\
+        Implement this trait in a `#[godot_api]` block. Every method has a default implementation, which is never called:\n\
+        only overridden methods are registered with Godot.",
+        class.name
+    );
+
+    let tokens = quote! {
+        #[doc = #doc]
+        #[allow(unused_variables)]
+        pub trait #trait_name: crate::obj::GodotClass<Base = #class_name> + crate::private::You_forgot_the_attribute__godot_api {
+            fn register_class(builder: &mut crate::builder::ClassBuilder<Self>) {}
+
+            fn init(base: crate::obj::Base<Self::Base>) -> Self {
+                unimplemented!()
+            }
+
+            fn to_string(&self) -> GodotString {
+                unimplemented!()
+            }
+
+            /// Called when the object receives a notification, after the base classes have handled it.
+            fn on_notification(&mut self, what: #notification_ident) {
+                unimplemented!()
+            }
+
+            /// Returns the value of the property `name`, or `None` to fall back to the regular property lookup.
+            fn get_property(&self, name: &StringName) -> Option<Variant> {
+                unimplemented!()
+            }
+
+            /// Assigns the property `name`. Returns `false` to fall back to the regular property assignment.
+            fn set_property(&mut self, name: &StringName, value: Variant) -> bool {
+                unimplemented!()
+            }
+
+            /// Additional properties, shown in the editor and saved with the object (depending on usage flags).
+            fn property_list(&self) -> Vec<crate::builtin::meta::PropertyInfo> {
+                unimplemented!()
+            }
+
+            /// Whether the property `name` can be reverted to a default value in the editor.
+            fn property_can_revert(&self, name: &StringName) -> bool {
+                unimplemented!()
+            }
+
+            /// The value that the property `name` is reverted to, or `None` if it has none.
+            fn property_get_revert(&self, name: &StringName) -> Option<Variant> {
+                unimplemented!()
+            }
+
+            #( #virtual_methods )*
+        }
+    };
+
+    (trait_name, tokens)
+}
+
+fn make_virtual_method_declaration(
+    method: &Method,
+    rust_name: &str,
+    ctx: &mut Context,
+) -> TokenStream {
+    let method_name = safe_ident(rust_name);
+    let (params, _) = make_params(&method.arguments, false, ctx);
+
+    let receiver = if method.is_const {
+        quote!(&self)
+    } else {
+        quote!(&mut self)
+    };
+
+    // Objects are returned as Gd<T> (not Option), since the value is passed directly to Godot
+    let return_decl = match method.return_value.as_ref() {
+        Some(ret) => {
+            let ty = to_rust_type(&ret.type_, ctx);
+            quote! { -> #ty }
+        }
+        None => TokenStream::new(),
+    };
+
+    quote! {
+        fn #method_name( #receiver #(, #params )* ) #return_decl {
+            unimplemented!()
+        }
+    }
+}
+
 fn make_enums(enums: &Option<Vec<ClassEnum>>, _class_name: &str, _ctx: &Context) -> TokenStream {
     let enums = match enums {
         Some(e) => e,
@@ -298,9 +601,7 @@ fn is_type_excluded(ty: &str, ctx: &mut Context) -> bool {
 fn is_method_excluded(method: &Method, #[allow(unused_variables)] ctx: &mut Context) -> bool {
     // Currently excluded:
     //
-    // * Private virtual methods designed for override; skip for now
-    //   E.g.: AudioEffectInstance::_process(const void*, AudioFrame*, int)
-    //   TODO decide what to do with them, overriding in a type-safe way?
+    // * Non-virtual methods starting with underscore, which are private to the engine
     //
     // * Methods accepting pointers are often supplementary
     //   E.g.: TextServer::font_set_data_ptr() -- in addition to TextServer::font_set_data().
@@ -322,7 +623,7 @@ fn is_method_excluded(method: &Method, #[allow(unused_variables)] ctx: &mut Cont
     }
     // -- end.
 
-    method.name.starts_with("_")
+    (method.name.starts_with('_') && !method.is_virtual)
         || method
             .return_value
             .as_ref()
@@ -351,14 +652,20 @@ fn is_function_excluded(function: &UtilityFunction, ctx: &mut Context) -> bool {
 }
 
 fn make_method_definition(method: &Method, class_name: &str, ctx: &mut Context) -> TokenStream {
-    if is_method_excluded(method, ctx) || special_cases::is_deleted(class_name, &method.name) {
+    // Virtual methods are not callable, but overridable through the {Class}Virtual traits
+    if method.is_virtual
+        || is_method_excluded(method, ctx)
+        || special_cases::is_deleted(class_name, &method.name)
+    {
         return TokenStream::new();
     }
 
     let is_varcall = method.is_vararg;
     let (params, arg_exprs) = make_params(&method.arguments, is_varcall, ctx);
 
+    // Renames only affect the Rust identifier; the engine looks up the method under its Godot name
     let method_name_str = special_cases::maybe_renamed(class_name, &method.name);
+    let godot_method_name = method.name.as_str();
     /*if method.map_args(|args| args.is_empty()) {
         // Getters (i.e. 0 arguments) will be stripped of their `get_` prefix, to conform to Rust convention
         if let Some(remainder) = method_name.strip_prefix("get_") {
@@ -393,7 +700,7 @@ fn make_method_definition(method: &Method, class_name: &str, ctx: &mut Context)
             #vis fn #method_name( #receiver #(, #params )*, varargs: &[Variant]) #return_decl {
                 unsafe {
                     let class_name = StringName::from(#class_name);
-                    let method_name = StringName::from(#method_name_str);
+                    let method_name = StringName::from(#godot_method_name);
                     let method_bind = sys::interface_fn!(classdb_get_method_bind)(
                         class_name.string_sys(),
                         method_name.string_sys(),
@@ -420,7 +727,7 @@ fn make_method_definition(method: &Method, class_name: &str, ctx: &mut Context)
             #vis fn #method_name( #receiver, #( #params ),* ) #return_decl {
                 unsafe {
                     let class_name = StringName::from(#class_name);
-                    let method_name = StringName::from(#method_name_str);
+                    let method_name = StringName::from(#godot_method_name);
                     let method_bind = sys::interface_fn!(classdb_get_method_bind)(
                         class_name.string_sys(),
                         method_name.string_sys(),
@@ -155,6 +155,8 @@ impl ToTokens for RustTy {
 struct GeneratedClass {
     tokens: TokenStream,
     inherits_macro_ident: Ident,
+    virtual_trait_ident: Ident,
+    notification_ident: Ident,
     has_pub_module: bool,
 }
 
@@ -162,6 +164,8 @@ struct GeneratedModule {
     class_ident: Ident,
     module_ident: Ident,
     inherits_macro_ident: Ident,
+    virtual_trait_ident: Ident,
+    notification_ident: Ident,
     is_pub: bool,
 }
 
@@ -180,15 +184,18 @@ const SELECTED_CLASSES: &[&str] = &[
     "Camera3D",
     "CanvasItem",
     "CanvasLayer",
+    "ClassDB",
     "CollisionObject2D",
     "CollisionShape2D",
     "Control",
+    "Engine",
     "Input",
     "OS",
     "FileAccess",
     "Label",
     "MainLoop",
     "Marker2D",
+    "Mutex",
     "Node",
     "Node2D",
     "Node3D",
@@ -202,7 +209,9 @@ const SELECTED_CLASSES: &[&str] = &[
     "ResourceLoader",
     "RigidBody2D",
     "SceneTree",
+    "Semaphore",
     "Sprite2D",
     "SpriteFrames",
+    "Thread",
     "Timer",
 ];
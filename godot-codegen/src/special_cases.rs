@@ -20,26 +20,14 @@ pub fn is_deleted(class_name: &str, method_name: &str) -> bool {
         //| ("Object", "to_string")
         | ("Object", "get_instance_id")
 
-        // Thread APIs
-        | ("ResourceLoader", "load_threaded_get")
-        | ("ResourceLoader", "load_threaded_get_status")
-        | ("ResourceLoader", "load_threaded_request")
-        // also: enum ThreadLoadStatus
-
         => true, _ => false
     }
 }
 
-#[rustfmt::skip]
-pub fn is_class_deleted(class_name: &str) -> bool {
-    match class_name {
-        // Thread APIs
-        | "Thread"
-        | "Mutex"
-        | "Semaphore"
-
-        => true, _ => false
-    }
+pub fn is_class_deleted(_class_name: &str) -> bool {
+    // No classes deleted at the moment.
+    // Thread APIs (Thread, Mutex, Semaphore) are available; calls into Rust objects from other threads are checked at runtime.
+    false
 }
 
 #[rustfmt::skip]
@@ -51,6 +39,15 @@ pub fn is_private(class_name: &str, method_name: &str) -> bool {
         | ("RefCounted", "reference")
         | ("RefCounted", "unreference")
 
+        // Wrapped by lock guards, see engine.rs
+        | ("Mutex", "lock")
+        | ("Mutex", "try_lock")
+        | ("Mutex", "unlock")
+
+        // Wrapped by Thread::spawn() and JoinHandle, see engine.rs
+        | ("Thread", "start")
+        | ("Thread", "wait_to_finish")
+
         => true, _ => false
     }
 }
@@ -58,6 +55,11 @@ pub fn is_private(class_name: &str, method_name: &str) -> bool {
 pub fn maybe_renamed<'c, 'm>(class_name: &'c str, method_name: &'m str) -> &'m str {
     match (class_name, method_name) {
         ("GDScript", "new") => "instantiate",
+        ("Mutex", "lock") => "raw_lock",
+        ("Mutex", "try_lock") => "raw_try_lock",
+        ("Mutex", "unlock") => "raw_unlock",
+        ("Thread", "start") => "raw_start",
+        ("Thread", "wait_to_finish") => "raw_wait_to_finish",
         _ => method_name,
     }
 }
@@ -5,7 +5,8 @@
  */
 
 use crate::builder::ClassBuilder;
-use crate::builtin::GodotString;
+use crate::builtin::meta::PropertyInfo;
+use crate::builtin::{GodotString, StringName, Variant};
 use crate::obj::Base;
 use crate::obj::GodotClass;
 
@@ -16,6 +17,7 @@ use crate::obj::GodotClass;
 /// * `to_string` method
 /// * Custom register methods (builder style)
 /// * All the lifecycle methods like `ready`, `process` etc.
+/// * Dynamic properties, which are not known at compile time (`get_property`, `set_property`, `property_list` etc.)
 ///
 /// This trait is special in that it needs to be used in combination with the `#[godot_api]`
 /// proc-macro attribute to ensure proper registration of its methods. All methods have
@@ -24,23 +26,15 @@ use crate::obj::GodotClass;
 ///
 /// Do not call any of these methods directly -- they are an interface to Godot. Functionality
 /// described here is available through other means (e.g. `init` via `Gd::new_default`).
+///
+/// This trait only covers the most common virtual methods. For all virtual methods of a given base class,
+/// with signatures checked against it, implement the corresponding trait in [`engine::virtuals`][crate::engine::virtuals]
+/// instead, e.g. `NodeVirtual` for classes inheriting `Node`.
 #[allow(unused_variables)]
 pub trait GodotExt: crate::private::You_forgot_the_attribute__godot_api
 where
     Self: GodotClass,
 {
-    // Note: keep in sync with VIRTUAL_METHOD_NAMES in godot_api.rs
-
-    // Some methods that were called:
-    // _enter_tree
-    // _input
-    // _shortcut_input
-    // _unhandled_input
-    // _unhandled_key_input
-    // _process
-    // _physics_process
-    // _ready
-
     fn register_class(builder: &mut ClassBuilder<Self>) {}
 
     fn init(base: Base<Self::Base>) -> Self {
@@ -59,4 +53,25 @@ where
     fn to_string(&self) -> GodotString {
         unimplemented!()
     }
+
+    /// Returns the value of the property `name`, or `None` to fall back to the regular property lookup.
+    fn get_property(&self, name: &StringName) -> Option<Variant> {
+        unimplemented!()
+    }
+    /// Assigns the property `name`. Returns `false` to fall back to the regular property assignment.
+    fn set_property(&mut self, name: &StringName, value: Variant) -> bool {
+        unimplemented!()
+    }
+    /// Additional properties, shown in the editor and the inspector, and saved with the object (depending on usage flags).
+    fn property_list(&self) -> Vec<PropertyInfo> {
+        unimplemented!()
+    }
+    /// Whether the property `name` can be reverted to a default value in the editor.
+    fn property_can_revert(&self, name: &StringName) -> bool {
+        unimplemented!()
+    }
+    /// The value that the property `name` is reverted to, or `None` if it has none.
+    fn property_get_revert(&self, name: &StringName) -> Option<Variant> {
+        unimplemented!()
+    }
 }
@@ -4,136 +4,181 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-// use crate::GodotClass;
-// use godot_core::Variant;
-// use godot_ffi as sys;
-// use std::borrow::Cow;
-// use std::ffi::CStr;
-
-// pub trait Method<C> {
-//     type ReturnType;
-//     type ParamTypes;
-//
-//     fn method_name(&self) -> Cow<CStr>;
-//     fn ptrcall(&mut self, instance: &mut C, args: Self::ParamTypes) -> Self::ReturnType;
-// }
-
-// ----------------------------------------------------------------------------------------------------------------------------------------------
-/*
-
-/// Method known at compile time (statically), either a Rust `fn` or closure.
-pub trait CodeMethod<C, R, Ps>
-where
-    C: GodotClass,
-{
-    const PARAM_COUNT: usize;
-    const NAME: &'static str;
-
-    unsafe fn varcall(
-        &mut self,
-        instance: sys::GDExtensionClassInstancePtr,
-        args: *const sys::GDNativeTypePtr,
-        ret: sys::GDNativeTypePtr,
-        err: *mut sys::GDNativeCallError,
-    );
-
-    unsafe fn ptrcall(
-        &mut self,
-        instance: sys::GDExtensionClassInstancePtr,
-        args: *const sys::GDNativeTypePtr,
-        ret: sys::GDNativeTypePtr,
-    );
+use crate::builtin::meta::{ClassName, PropertyInfo, SignatureTuple};
+use crate::builtin::StringName;
+use crate::obj::GodotClass;
+use godot_ffi as sys;
+use std::panic::AssertUnwindSafe;
+use sys::VariantType;
+
+/// Function or closure which can be registered as a method of class `C` with [`ClassBuilder::method()`][super::ClassBuilder::method].
+///
+/// Implemented for `Fn(&mut C, P0, P1, ...) -> R` with up to 10 parameters, where all `Pi` and `R` can be converted
+/// from/to `Variant`. The type parameter `Ps` is the tuple of parameter types; it only serves to distinguish the impls.
+pub trait Method<C, Ps>: 'static {
+    #[doc(hidden)]
+    type Sig: SignatureTuple;
+
+    #[doc(hidden)]
+    fn call(
+        &self,
+        instance: &mut C,
+        params: <Self::Sig as SignatureTuple>::Params,
+    ) -> <Self::Sig as SignatureTuple>::Ret;
 }
 
+macro_rules! impl_method_for_fn {
+    ( $( $Pn:ident $pn:ident ),* ) => {
+        impl<C, F, R, $( $Pn, )*> Method<C, ( $( $Pn, )* )> for F
+        where
+            F: Fn(&mut C, $( $Pn, )* ) -> R + 'static,
+            (R, $( $Pn, )*): SignatureTuple<Params = ( $( $Pn, )* ), Ret = R>,
+        {
+            type Sig = (R, $( $Pn, )*);
 
-// TODO code duplication ((2))
-macro_rules! count_idents {
-    () => {
-        0
+            fn call(&self, instance: &mut C, ( $( $pn, )* ): ( $( $Pn, )* )) -> R {
+                self(instance, $( $pn, )*)
+            }
+        }
     };
-    ($name:ident, $($other:ident,)*) => {
-        1 + $crate::gdext_count_idents!($($other,)*)
-    }
 }
 
-macro_rules! impl_code_method {
-// 	( $( $Param:ident ),* ) => {
-    ( $( $Param:ident $arg:ident ),* ) => {
-        impl<C, F, R, $( $Param ),*> CodeMethod<C, R, ( $( $Param, )* )> for F
-        where
-            C: $crate::GodotClass + $crate::GodotDefault, // TODO only GodotClass
-            F: Fn(&C, $( $Param ),* ) -> R,
-            $(
-                $Param: sys::GodotFfi,
-            )*
-            R: sys::GodotFfi + 'static,
-        {
-            const PARAM_COUNT: usize = count_idents!($( $Param, )*);
-
-            // Varcall
-            #[inline]
-            #[allow(unused_variables, unused_assignments, unused_mut)]
-            unsafe fn varcall(
-                &mut self,
-                instance: sys::GDExtensionClassInstancePtr,
-                args: *const sys::GDNativeTypePtr,
-                ret: sys::GDNativeTypePtr,
-                err: *mut sys::GDNativeCallError,
-            ) {
-                let storage = ::godot_core::private::as_storage::<C>(instance);
-                let instance = storage.get_mut_lateinit();
-
-                let mut idx = 0;
-
-                $(
-                    let $arg = <$Param as FromVariant::from_variant(&*(*args.offset(idx) as *mut Variant));
-                    idx += 1;
-                )*
-
-                let ret_val = self(&instance, $(
-                    $arg,
-                )*);
-
-                *(ret as *mut Variant) = Variant::from(ret_val);
-                (*err).error = sys::GDNativeCallErrorType_GDNATIVE_CALL_OK;
-            }
+impl_method_for_fn!();
+impl_method_for_fn!(P0 p0);
+impl_method_for_fn!(P0 p0, P1 p1);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3, P4 p4);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3, P4 p4, P5 p5);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6, P7 p7);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6, P7 p7, P8 p8);
+impl_method_for_fn!(P0 p0, P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6, P7 p7, P8 p8, P9 p9);
 
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Registration
 
-            // Ptrcall
-            #[inline]
-            #[allow(unused_variables, unused_assignments, unused_mut)]
-            unsafe fn ptrcall(
-                &mut self,
-                instance: sys::GDExtensionClassInstancePtr,
-                args: *const sys::GDNativeTypePtr,
-                ret: sys::GDNativeTypePtr,
-            ) {
-                let storage = $crate::private::as_storage::<C>(instance);
-                let instance = storage.get_mut_lateinit();
-
-                // TODO reuse code, see ((1))
-                let mut idx = 0;
-
-                $(
-                    let $arg = <$Param as sys::GodotFfi>::from_sys(*args.offset(idx));
-                    // FIXME update refcount, e.g. Gd::ready() or T::Mem::maybe_inc_ref(&result);
-                    // possibly in from_sys() directly; what about from_sys_init() and from_{obj|str}_sys()?
-                    idx += 1;
-                )*
-
-                let ret_val = self(&instance, $(
-                    $arg,
-                )*);
-
-                <R as sys::GodotFfi>::write_sys(&ret_val, ret);
-            }
-        }
+/// Passed to Godot as method userdata. Never freed, as Godot keeps methods registered until the extension is unloaded.
+struct MethodData<F> {
+    name: String,
+    method: F,
+}
 
+/// Registers `method` under `name` in class `C`, dispatching both varcalls and ptrcalls to it.
+pub(super) fn register_method<C, Ps, F>(class_name: &ClassName, name: &str, method: F)
+where
+    C: GodotClass,
+    F: Method<C, Ps>,
+{
+    let data: &'static mut MethodData<F> = Box::leak(Box::new(MethodData {
+        name: name.to_string(),
+        method,
+    }));
+
+    // Return value meta-information
+    let has_return_value = <F::Sig as SignatureTuple>::variant_type(-1) != VariantType::Nil;
+    let return_value_info = <F::Sig as SignatureTuple>::property_info(-1, "");
+    let mut return_value_info_sys = return_value_info.property_sys();
+    let return_value_metadata = <F::Sig as SignatureTuple>::param_metadata(-1);
+
+    // Arguments meta-information; parameter names are not known at runtime
+    let param_count = <F::Sig as SignatureTuple>::PARAM_COUNT;
+    let arguments_info: Vec<PropertyInfo> = (0..param_count as i32)
+        .map(|i| <F::Sig as SignatureTuple>::property_info(i, &format!("arg{i}")))
+        .collect();
+    let mut arguments_info_sys: Vec<sys::GDNativePropertyInfo> = arguments_info
+        .iter()
+        .map(|info| info.property_sys())
+        .collect();
+    let mut arguments_metadata: Vec<sys::GDNativeExtensionClassMethodArgumentMetadata> = (0
+        ..param_count as i32)
+        .map(|i| <F::Sig as SignatureTuple>::param_metadata(i))
+        .collect();
+
+    let method_name = StringName::from(name);
+    let method_info = sys::GDNativeExtensionClassMethodInfo {
+        name: method_name.string_sys(),
+        method_userdata: data as *mut MethodData<F> as *mut std::ffi::c_void,
+        call_func: Some(varcall::<C, Ps, F>),
+        ptrcall_func: Some(ptrcall::<C, Ps, F>),
+        method_flags: sys::GDNATIVE_EXTENSION_METHOD_FLAGS_DEFAULT as u32,
+        has_return_value: has_return_value as u8,
+        return_value_info: std::ptr::addr_of_mut!(return_value_info_sys),
+        return_value_metadata,
+        argument_count: param_count as u32,
+        arguments_info: arguments_info_sys.as_mut_ptr(),
+        arguments_metadata: arguments_metadata.as_mut_ptr(),
+        default_argument_count: 0,
+        default_arguments: std::ptr::null_mut(),
     };
+
+    crate::out!("   Register fn:   {}::{}", class_name, name);
+    unsafe {
+        sys::interface_fn!(classdb_register_extension_class_method)(
+            sys::get_library(),
+            class_name.string_sys(),
+            std::ptr::addr_of!(method_info),
+        );
+    }
+}
+
+unsafe extern "C" fn varcall<C, Ps, F>(
+    method_data: *mut std::ffi::c_void,
+    instance_ptr: sys::GDExtensionClassInstancePtr,
+    args: *const sys::GDNativeVariantPtr,
+    _arg_count: sys::GDNativeInt,
+    ret: sys::GDNativeVariantPtr,
+    err: *mut sys::GDNativeCallError,
+) where
+    C: GodotClass,
+    F: Method<C, Ps>,
+{
+    let data = &*(method_data as *const MethodData<F>);
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        <F::Sig as SignatureTuple>::varcall::<C>(
+            instance_ptr,
+            args,
+            ret,
+            err,
+            |inst, params| data.method.call(inst, params),
+            &data.name,
+        )
+    }));
+
+    if let Err(e) = result {
+        crate::log::godot_error!("Rust function panicked: {}", data.name);
+        crate::private::print_panic(e);
+
+        // Signal error and set return type to Nil
+        (*err).error = sys::GDNATIVE_CALL_ERROR_INVALID_METHOD; // no better fitting enum?
+        sys::interface_fn!(variant_new_nil)(ret);
+    }
 }
 
-impl_code_method!();
-impl_code_method!(P0 arg0);
-impl_code_method!(P0 arg0, P1 arg1);
-impl_code_method!(P0 arg0, P1 arg1, P2 arg2);
-*/
+unsafe extern "C" fn ptrcall<C, Ps, F>(
+    method_data: *mut std::ffi::c_void,
+    instance_ptr: sys::GDExtensionClassInstancePtr,
+    args: *const sys::GDNativeTypePtr,
+    ret: sys::GDNativeTypePtr,
+) where
+    C: GodotClass,
+    F: Method<C, Ps>,
+{
+    let data = &*(method_data as *const MethodData<F>);
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        <F::Sig as SignatureTuple>::ptrcall::<C>(
+            instance_ptr,
+            args,
+            ret,
+            |inst, params| data.method.call(inst, params),
+            &data.name,
+        )
+    }));
+
+    if let Err(e) = result {
+        crate::log::godot_error!("Rust function panicked: {}", data.name);
+        crate::private::print_panic(e);
+    }
+}
@@ -4,12 +4,26 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+//! Registration of methods, properties, signals and constants at runtime.
+//!
+//! A [`ClassBuilder`] is passed to the `register_class` function of `GodotExt` (or a virtual trait like `NodeVirtual`).
+//! This allows registering symbols which are not known at compile time, e.g. when they are generated from data files,
+//! without the `#[func]` and `#[signal]` attributes.
+
+use crate::builtin::meta::{ClassName, PropertyInfo, VariantMetadata};
+use crate::builtin::{FromVariant, GodotString, StringName, ToVariant};
+use crate::engine::global::{PropertyHint, PropertyUsageFlags};
 use crate::obj::GodotClass;
+use godot_ffi as sys;
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
 mod method;
 
+pub use method::Method;
+
 pub struct ClassBuilder<C> {
+    class_name: ClassName,
     _c: PhantomData<C>,
 }
 
@@ -18,32 +32,178 @@ where
     C: GodotClass,
 {
     pub(crate) fn new() -> Self {
-        Self { _c: PhantomData }
+        Self {
+            class_name: ClassName::new::<C>(),
+            _c: PhantomData,
+        }
     }
 
-    pub fn virtual_method<'cb, F>(&'cb mut self, name: &'cb str, method: F) -> MethodBuilder<C, F> {
+    /// Registers a method, callable from GDScript and other languages.
+    ///
+    /// `method` is a function or closure taking `&mut C` as first parameter, followed by up to 10 parameters:
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// # use godot::builder::ClassBuilder;
+    /// # #[derive(GodotClass)] #[class(init)] struct Calculator {}
+    /// # fn register(builder: &mut ClassBuilder<Calculator>) {
+    /// builder
+    ///     .method("add", |_this: &mut Calculator, a: i64, b: i64| a + b)
+    ///     .done();
+    /// # }
+    /// ```
+    pub fn method<'cb, Ps, F>(
+        &'cb mut self,
+        name: &'cb str,
+        method: F,
+    ) -> MethodBuilder<'cb, C, Ps, F>
+    where
+        F: Method<C, Ps>,
+    {
         MethodBuilder::new(self, name, method)
     }
+
+    /// Registers a property with the given getter and setter.
+    ///
+    /// The accessors are registered as methods `get_{name}` and `set_{name}`.
+    pub fn property<'cb, T, G, S>(
+        &'cb mut self,
+        name: &'cb str,
+        getter: G,
+        setter: S,
+    ) -> PropertyBuilder<'cb, C, T, G, S>
+    where
+        T: VariantMetadata + ToVariant + FromVariant + sys::GodotFuncMarshal + Debug + 'static,
+        G: Fn(&C) -> T + 'static,
+        S: Fn(&mut C, T) + 'static,
+    {
+        PropertyBuilder::new(self, name, getter, setter)
+    }
+
+    /// Registers a signal with the given parameters, e.g. `i64::property_info("amount")`.
+    pub fn signal(&mut self, name: &str, params: &[PropertyInfo]) {
+        let signal_name = StringName::from(name);
+        let params_sys: Vec<sys::GDNativePropertyInfo> =
+            params.iter().map(|param| param.property_sys()).collect();
+
+        unsafe {
+            sys::interface_fn!(classdb_register_extension_class_signal)(
+                sys::get_library(),
+                self.class_name.string_sys(),
+                signal_name.string_sys(),
+                params_sys.as_ptr(),
+                params_sys.len() as sys::GDNativeInt,
+            );
+        }
+    }
+
+    /// Registers an integer constant, accessible as `MyClass.NAME` in GDScript.
+    pub fn constant(&mut self, name: &str, value: i64) {
+        crate::registry::register_integer_constant(&self.class_name, "", name, value, false);
+    }
 }
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
-#[allow(dead_code)] // TODO rm
 #[must_use]
-pub struct MethodBuilder<'cb, C, F> {
+pub struct MethodBuilder<'cb, C, Ps, F> {
     class_builder: &'cb mut ClassBuilder<C>,
     name: &'cb str,
     method: F,
+    _params: PhantomData<Ps>,
 }
 
-impl<'cb, C, F> MethodBuilder<'cb, C, F> {
+impl<'cb, C, Ps, F> MethodBuilder<'cb, C, Ps, F>
+where
+    C: GodotClass,
+    F: Method<C, Ps>,
+{
     pub(super) fn new(class_builder: &'cb mut ClassBuilder<C>, name: &'cb str, method: F) -> Self {
         Self {
             class_builder,
             name,
             method,
+            _params: PhantomData,
         }
     }
 
-    pub fn done(self) {}
+    /// Registers the method with Godot.
+    pub fn done(self) {
+        method::register_method(&self.class_builder.class_name, self.name, self.method);
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[must_use]
+pub struct PropertyBuilder<'cb, C, T, G, S> {
+    class_builder: &'cb mut ClassBuilder<C>,
+    name: &'cb str,
+    getter: G,
+    setter: S,
+    info: PropertyInfo,
+    _value: PhantomData<T>,
+}
+
+impl<'cb, C, T, G, S> PropertyBuilder<'cb, C, T, G, S>
+where
+    C: GodotClass,
+    T: VariantMetadata + ToVariant + FromVariant + sys::GodotFuncMarshal + Debug + 'static,
+    G: Fn(&C) -> T + 'static,
+    S: Fn(&mut C, T) + 'static,
+{
+    pub(super) fn new(
+        class_builder: &'cb mut ClassBuilder<C>,
+        name: &'cb str,
+        getter: G,
+        setter: S,
+    ) -> Self {
+        Self {
+            class_builder,
+            name,
+            getter,
+            setter,
+            info: T::property_info(name),
+            _value: PhantomData,
+        }
+    }
+
+    /// Sets the editor hint, e.g. `PROPERTY_HINT_RANGE` with hint string `"0,100"`.
+    pub fn hint(mut self, hint: PropertyHint, hint_string: impl Into<GodotString>) -> Self {
+        self.info = self.info.with_hint(hint, hint_string);
+        self
+    }
+
+    /// Sets the usage flags. By default, properties are shown in the editor and saved.
+    pub fn usage(mut self, usage: PropertyUsageFlags) -> Self {
+        self.info = self.info.with_usage(usage);
+        self
+    }
+
+    /// Registers the property and its accessors with Godot.
+    pub fn done(self) {
+        let class_name = &self.class_builder.class_name;
+        let getter_name = format!("get_{}", self.name);
+        let setter_name = format!("set_{}", self.name);
+
+        let getter = self.getter;
+        let setter = self.setter;
+        method::register_method(class_name, &getter_name, move |this: &mut C| getter(&*this));
+        method::register_method(class_name, &setter_name, move |this: &mut C, value: T| {
+            setter(this, value)
+        });
+
+        let getter_name = StringName::from(getter_name.as_str());
+        let setter_name = StringName::from(setter_name.as_str());
+        let info_sys = self.info.property_sys();
+
+        unsafe {
+            sys::interface_fn!(classdb_register_extension_class_property)(
+                sys::get_library(),
+                class_name.string_sys(),
+                std::ptr::addr_of!(info_sys),
+                setter_name.string_sys(),
+                getter_name.string_sys(),
+            );
+        }
+    }
 }
@@ -43,6 +43,28 @@ impl_builtin_froms!(StringArray; Array => packed_string_array_from_array);
 impl_builtin_froms!(Vector2Array; Array => packed_vector2_array_from_array);
 impl_builtin_froms!(Vector3Array; Array => packed_vector3_array_from_array);
 
+impl Default for Array {
+    fn default() -> Self {
+        unsafe {
+            Self::from_sys_init(|self_ptr| {
+                let ctor = sys::method_table().array_construct_default;
+                ctor(self_ptr, std::ptr::null_mut());
+            })
+        }
+    }
+}
+
+impl Default for StringArray {
+    fn default() -> Self {
+        unsafe {
+            Self::from_sys_init(|self_ptr| {
+                let ctor = sys::method_table().packed_string_array_construct_default;
+                ctor(self_ptr, std::ptr::null_mut());
+            })
+        }
+    }
+}
+
 impl Array {
     pub fn get(&self, index: i64) -> Option<Variant> {
         unsafe {
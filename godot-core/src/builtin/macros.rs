@@ -12,9 +12,10 @@ macro_rules! impl_builtin_traits_inner {
             #[inline]
             fn default() -> Self {
                 unsafe {
-                    let mut gd_val = sys::$GdType::default();
-                    (sys::method_table().$gd_method)(&mut gd_val);
-                    <$Type>::from_sys(gd_val)
+                    Self::from_sys_init(|self_ptr| {
+                        let ctor = sys::method_table().$gd_method;
+                        ctor(self_ptr, std::ptr::null_mut());
+                    })
                 }
             }
         }
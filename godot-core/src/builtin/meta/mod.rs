@@ -65,6 +65,27 @@ impl PropertyInfo {
         }
     }
 
+    /// Sets the editor hint, e.g. `PROPERTY_HINT_RANGE` with hint string `"0,100"`.
+    pub fn with_hint(
+        mut self,
+        hint: global::PropertyHint,
+        hint_string: impl Into<GodotString>,
+    ) -> Self {
+        self.hint = hint;
+        self.hint_string = hint_string.into();
+        self
+    }
+
+    /// Sets the usage flags, e.g. `PROPERTY_USAGE_STORAGE` for properties which are saved, but not shown in the editor.
+    pub fn with_usage(mut self, usage: global::PropertyUsageFlags) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub fn property_name(&self) -> &StringName {
+        &self.property_name
+    }
+
     /// Converts to the FFI type. Keep this object allocated while using that!
     pub fn property_sys(&self) -> sys::GDNativePropertyInfo {
         use crate::obj::EngineEnum as _;
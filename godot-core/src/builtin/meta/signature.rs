@@ -12,6 +12,9 @@ pub trait SignatureTuple {
     type Params;
     type Ret;
 
+    /// Number of parameters, excluding the return type.
+    const PARAM_COUNT: usize;
+
     fn variant_type(index: i32) -> VariantType;
     fn property_info(index: i32, param_name: &str) -> PropertyInfo;
     fn param_metadata(index: i32) -> sys::GDNativeExtensionClassMethodArgumentMetadata;
@@ -21,8 +24,8 @@ pub trait SignatureTuple {
         args_ptr: *const sys::GDNativeVariantPtr,
         ret: sys::GDNativeVariantPtr,
         err: *mut sys::GDNativeCallError,
-        func: fn(&mut C, Self::Params) -> Self::Ret,
-        method_name: &str,
+        func: impl FnOnce(&mut C, Self::Params) -> Self::Ret,
+        method_name: &'static str,
     );
 
     // Note: this method imposes extra bounds on GodotFfi, which may not be implemented for user types.
@@ -31,8 +34,8 @@ pub trait SignatureTuple {
         instance_ptr: sys::GDExtensionClassInstancePtr,
         args_ptr: *const sys::GDNativeTypePtr,
         ret: sys::GDNativeTypePtr,
-        func: fn(&mut C, Self::Params) -> Self::Ret,
-        method_name: &str,
+        func: impl FnOnce(&mut C, Self::Params) -> Self::Ret,
+        method_name: &'static str,
     );
 }
 
@@ -55,6 +58,7 @@ pub trait SignatureTuple {
 use crate::builtin::meta::*;
 use crate::builtin::{FromVariant, ToVariant, Variant};
 use crate::obj::GodotClass;
+use crate::storage::BindSite;
 
 macro_rules! impl_signature_for_tuple {
     (
@@ -69,6 +73,8 @@ macro_rules! impl_signature_for_tuple {
             type Params = ($($Pn,)*);
             type Ret = $R;
 
+            const PARAM_COUNT: usize = 0 $( + ($n - $n + 1) )*;
+
             #[inline]
             fn variant_type(index: i32) -> sys::VariantType {
                 match index {
@@ -109,13 +115,13 @@ macro_rules! impl_signature_for_tuple {
                 args_ptr: *const sys::GDNativeVariantPtr,
                 ret: sys::GDNativeVariantPtr,
                 err: *mut sys::GDNativeCallError,
-                func: fn(&mut C, Self::Params) -> Self::Ret,
-                method_name: &str,
+                func: impl FnOnce(&mut C, Self::Params) -> Self::Ret,
+                method_name: &'static str,
             ) {
     	        $crate::out!("varcall: {}", method_name);
 
                 let storage = unsafe { crate::private::as_storage::<C>(instance_ptr) };
-                let mut instance = storage.get_mut();
+                let mut instance = storage.get_mut(BindSite::Method(method_name));
 
                 let args = ( $(
                     {
@@ -140,13 +146,13 @@ macro_rules! impl_signature_for_tuple {
 				instance_ptr: sys::GDExtensionClassInstancePtr,
                 args_ptr: *const sys::GDNativeTypePtr,
                 ret: sys::GDNativeTypePtr,
-                func: fn(&mut C, Self::Params) -> Self::Ret,
-                method_name: &str,
+                func: impl FnOnce(&mut C, Self::Params) -> Self::Ret,
+                method_name: &'static str,
             ) {
                 $crate::out!("ptrcall: {}", method_name);
 
                 let storage = unsafe { crate::private::as_storage::<C>(instance_ptr) };
-                let mut instance = storage.get_mut();
+                let mut instance = storage.get_mut(BindSite::Method(method_name));
 
 				let args = ( $(
                     unsafe { <$Pn as sys::GodotFuncMarshal>::try_from_sys(*args_ptr.offset($n)) }
@@ -164,14 +170,14 @@ macro_rules! impl_signature_for_tuple {
     };
 }
 
-fn param_error<P>(method_name: &str, index: i32, arg: &impl Debug) -> ! {
+fn param_error<P>(method_name: &'static str, index: i32, arg: &impl Debug) -> ! {
     let param_ty = std::any::type_name::<P>();
     panic!(
         "{method_name}: parameter [{index}] has type {param_ty}, which is unable to store argument {arg:?}",
     );
 }
 
-fn return_error<R>(method_name: &str, arg: &impl Debug) -> ! {
+fn return_error<R>(method_name: &'static str, arg: &impl Debug) -> ! {
     let return_ty = std::any::type_name::<R>();
     panic!("{method_name}: return type {return_ty} is unable to store value {arg:?}",);
 }
@@ -14,12 +14,15 @@ mod node_path;
 mod others;
 mod string;
 mod string_name;
-mod variant;
 mod vector2;
 mod vector3;
 mod vector4;
 
+#[cfg(feature = "serde")]
+mod serialize;
+
 pub mod meta;
+pub mod variant;
 
 pub use arrays::*;
 pub use color::*;
@@ -4,16 +4,119 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use crate::builtin::GodotString;
+use crate::builtin::{GodotString, StringName, ToVariant, Variant};
 use godot_ffi as sys;
 use godot_ffi::{ffi_methods, GodotFfi};
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
 
 pub struct NodePath {
     opaque: sys::types::OpaqueNodePath,
 }
 
 impl NodePath {
+    /// Whether the path starts at the root (`/root/Main`), as opposed to being relative to a node.
+    pub fn is_absolute(&self) -> bool {
+        self.call("is_absolute", &[]).to::<bool>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.call("is_empty", &[]).to::<bool>()
+    }
+
+    /// Number of node names, e.g. 2 for `Path2D/PathFollow2D:position:x`.
+    pub fn get_name_count(&self) -> usize {
+        self.call("get_name_count", &[]).to::<i64>() as usize
+    }
+
+    /// Node name at `index`, e.g. `PathFollow2D` for index 1 in `Path2D/PathFollow2D:position:x`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn get_name(&self, index: usize) -> StringName {
+        let count = self.get_name_count();
+        assert!(
+            index < count,
+            "name index {index} out of bounds (count {count})"
+        );
+
+        self.call("get_name", &[(index as i64).to_variant()])
+            .to::<StringName>()
+    }
+
+    /// Number of subnames (resource or property names), e.g. 2 for `Path2D/PathFollow2D:position:x`.
+    pub fn get_subname_count(&self) -> usize {
+        self.call("get_subname_count", &[]).to::<i64>() as usize
+    }
+
+    /// Subname at `index`, e.g. `x` for index 1 in `Path2D/PathFollow2D:position:x`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn get_subname(&self, index: usize) -> StringName {
+        let count = self.get_subname_count();
+        assert!(
+            index < count,
+            "subname index {index} out of bounds (count {count})"
+        );
+
+        self.call("get_subname", &[(index as i64).to_variant()])
+            .to::<StringName>()
+    }
+
+    /// All node names joined by `/`, e.g. `Path2D/PathFollow2D` for `Path2D/PathFollow2D:position:x`.
+    pub fn get_concatenated_names(&self) -> StringName {
+        self.call("get_concatenated_names", &[]).to::<StringName>()
+    }
+
+    /// All subnames joined by `:`, e.g. `position:x` for `Path2D/PathFollow2D:position:x`.
+    pub fn get_concatenated_subnames(&self) -> StringName {
+        self.call("get_concatenated_subnames", &[])
+            .to::<StringName>()
+    }
+
+    /// Same path, but with only the subnames, e.g. `:position:x` for `Path2D/PathFollow2D:position:x`.
+    pub fn get_as_property_path(&self) -> NodePath {
+        self.call("get_as_property_path", &[]).to::<NodePath>()
+    }
+
+    /// Appends `other` to this path, like `Path::join()` in the standard library.
+    ///
+    /// If `other` is absolute, it replaces this path. Subnames of `other` are kept, so
+    /// `Player` joined with `Sprite2D:position` results in `Player/Sprite2D:position`.
+    ///
+    /// # Panics
+    /// If this path has subnames and `other` is not empty, since nodes cannot be nested below properties.
+    pub fn join(&self, other: &NodePath) -> NodePath {
+        if other.is_absolute() || self.is_empty() {
+            return other.clone();
+        }
+        if other.is_empty() {
+            return self.clone();
+        }
+
+        assert_eq!(
+            self.get_subname_count(),
+            0,
+            "cannot append `{other}` to path `{self}` with subnames"
+        );
+
+        let base = GodotString::from(self).to_string();
+        let appended = GodotString::from(other).to_string();
+        let joined = if appended.starts_with(':') {
+            // `other` is a pure property path
+            format!("{base}{appended}")
+        } else {
+            format!("{}/{appended}", base.trim_end_matches('/'))
+        };
+
+        NodePath::from(joined.as_str())
+    }
+
+    fn call(&self, method: &str, args: &[Variant]) -> Variant {
+        self.to_variant().call_builtin(method, args)
+    }
+
     fn from_opaque(opaque: sys::types::OpaqueNodePath) -> Self {
         Self { opaque }
     }
@@ -60,9 +163,35 @@ impl Display for NodePath {
     }
 }
 
+impl Debug for NodePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let string = GodotString::from(self);
+        <GodotString as Debug>::fmt(&string, f)
+    }
+}
+
+impl Default for NodePath {
+    fn default() -> Self {
+        unsafe {
+            Self::from_sys_init(|self_ptr| {
+                let ctor = sys::method_table().node_path_construct_default;
+                ctor(self_ptr, std::ptr::null_mut());
+            })
+        }
+    }
+}
+
 impl_builtin_traits! {
     for NodePath {
         Clone => node_path_construct_copy;
         Drop => node_path_destroy;
+        Eq => node_path_operator_equal;
+    }
+}
+
+// Equal paths have the same string representation, so this is consistent with Eq
+impl Hash for NodePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        GodotString::from(self).hash(state)
     }
 }
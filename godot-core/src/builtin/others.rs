@@ -27,6 +27,30 @@ impl_builtin_stub!(Callable, OpaqueCallable);
 impl_builtin_stub!(Signal, OpaqueSignal);
 impl_builtin_stub!(Dictionary, OpaqueDictionary);
 
+impl_builtin_traits! {
+    for Rect2 {
+        Default => rect2_construct_default;
+    }
+}
+
+impl_builtin_traits! {
+    for Quaternion {
+        Default => quaternion_construct_default;
+    }
+}
+
+impl_builtin_traits! {
+    for Transform2D {
+        Default => transform2d_construct_default;
+    }
+}
+
+impl_builtin_traits! {
+    for Transform3D {
+        Default => transform3d_construct_default;
+    }
+}
+
 #[repr(C)]
 struct InnerRect {
     position: Vector2,
@@ -43,6 +67,17 @@ impl Rect2 {
     }
 }
 
+impl Default for Dictionary {
+    fn default() -> Self {
+        unsafe {
+            Self::from_sys_init(|self_ptr| {
+                let ctor = sys::method_table().dictionary_construct_default;
+                ctor(self_ptr, std::ptr::null_mut());
+            })
+        }
+    }
+}
+
 impl Callable {
     pub fn from_object_method<T, S>(object: Gd<T>, method: S) -> Self
     where
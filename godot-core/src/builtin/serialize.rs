@@ -0,0 +1,189 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `serde` support for builtin types which cannot simply derive it.
+//!
+//! Types backed by plain Rust data (vectors, `Color`) derive their impls directly. Types in this module either
+//! need the engine (strings, containers, `Variant`), or are stored as opaque Godot memory.
+//!
+//! `Variant`, `Array` and `Dictionary` go through [`encoding::Value`], which is self-describing: every value
+//! carries its variant type, so arbitrary variant trees round-trip through formats like JSON or RON.
+
+use crate::builtin::variant::encoding::Value;
+use crate::builtin::*;
+use godot_ffi as sys;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Strings
+
+impl Serialize for GodotString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&String::from(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for GodotString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(GodotString::from)
+    }
+}
+
+impl Serialize for StringName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GodotString::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StringName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        GodotString::deserialize(deserializer).map(|s| StringName::from(&s))
+    }
+}
+
+impl Serialize for NodePath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GodotString::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NodePath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        GodotString::deserialize(deserializer).map(|s| NodePath::from(&s))
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Opaque math types
+
+// Math types which are still opaque stubs are (de)serialized through a mirror struct with identical memory layout.
+macro_rules! impl_serde_via_repr {
+    ($Type:ty, $Repr:ty) => {
+        sys::static_assert_eq_size!($Type, $Repr);
+
+        impl Serialize for $Type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let repr: $Repr = unsafe { std::mem::transmute(*self) };
+                repr.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $Type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let repr = <$Repr>::deserialize(deserializer)?;
+                Ok(unsafe { std::mem::transmute(repr) })
+            }
+        }
+    };
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Rect2")]
+#[repr(C)]
+struct Rect2Repr {
+    position: Vector2,
+    size: Vector2,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Quaternion")]
+#[repr(C)]
+struct QuaternionRepr {
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Transform2D")]
+#[repr(C)]
+struct Transform2DRepr {
+    x: Vector2,
+    y: Vector2,
+    origin: Vector2,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Transform3D")]
+#[repr(C)]
+struct Transform3DRepr {
+    /// Rows of the basis, like Godot stores them.
+    basis: [Vector3; 3],
+    origin: Vector3,
+}
+
+impl_serde_via_repr!(Rect2, Rect2Repr);
+impl_serde_via_repr!(Quaternion, QuaternionRepr);
+impl_serde_via_repr!(Transform2D, Transform2DRepr);
+impl_serde_via_repr!(Transform3D, Transform3DRepr);
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Variant and containers
+
+impl Serialize for Variant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        to_value(self)?.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(|value| value.to_variant())
+    }
+}
+
+impl Serialize for Array {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match to_value(&self.to_variant())? {
+            Value::Array(elements) => elements.serialize(serializer),
+            _ => unreachable!("Array must be encoded as array"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Array {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let elements = Vec::<Value>::deserialize(deserializer)?;
+        from_value(Value::Array(elements))
+    }
+}
+
+impl Serialize for Dictionary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match to_value(&self.to_variant())? {
+            Value::Dictionary(pairs) => pairs.serialize(serializer),
+            _ => unreachable!("Dictionary must be encoded as dictionary"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Dictionary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = Vec::<(Value, Value)>::deserialize(deserializer)?;
+        from_value(Value::Dictionary(pairs))
+    }
+}
+
+fn to_value<E: ser::Error>(variant: &Variant) -> Result<Value, E> {
+    Value::try_from_variant(variant).map_err(|_| {
+        E::custom(format_args!(
+            "variant of type {:?} cannot be serialized",
+            variant.get_type()
+        ))
+    })
+}
+
+fn from_value<T: FromVariant, E: de::Error>(value: Value) -> Result<T, E> {
+    let variant = value.to_variant();
+    T::try_from_variant(&variant).map_err(|_| {
+        E::custom(format_args!(
+            "variant of type {:?} cannot be deserialized as {}",
+            variant.get_type(),
+            std::any::type_name::<T>()
+        ))
+    })
+}
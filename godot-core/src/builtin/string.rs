@@ -4,8 +4,11 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{convert::Infallible, fmt, str::FromStr};
+use std::hash::{Hash, Hasher};
+use std::ops::RangeBounds;
+use std::{convert::Infallible, fmt, ptr, slice, str::FromStr};
 
+use crate::builtin::{StringArray, ToVariant, Variant, VariantOperator};
 use godot_ffi as sys;
 use sys::types::OpaqueString;
 use sys::{ffi_methods, interface_fn, GodotFfi};
@@ -20,6 +23,154 @@ impl GodotString {
         Self::default()
     }
 
+    /// Number of characters (Unicode code points) in the string.
+    ///
+    /// Same as `length()` in GDScript; this is not the UTF-8 byte count.
+    pub fn len(&self) -> usize {
+        let len =
+            unsafe { interface_fn!(string_to_utf32_chars)(self.string_sys(), ptr::null_mut(), 0) };
+
+        assert!(len >= 0);
+        len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the characters, reading directly from Godot's UTF-32 buffer.
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            units: self.utf32().iter(),
+        }
+    }
+
+    /// Character index of the first occurrence of `what`, or `None` if not found.
+    pub fn find(&self, what: &str) -> Option<usize> {
+        let units = self.utf32();
+        let what_len = what.chars().count();
+        if what_len > units.len() {
+            return None;
+        }
+
+        (0..=units.len() - what_len).find(|&start| matches_at(units, start, what))
+    }
+
+    /// Character index of the last occurrence of `what`, or `None` if not found.
+    pub fn rfind(&self, what: &str) -> Option<usize> {
+        let units = self.utf32();
+        let what_len = what.chars().count();
+        if what_len > units.len() {
+            return None;
+        }
+
+        (0..=units.len() - what_len)
+            .rev()
+            .find(|&start| matches_at(units, start, what))
+    }
+
+    pub fn begins_with(&self, prefix: &str) -> bool {
+        let units = self.utf32();
+        prefix.chars().count() <= units.len() && matches_at(units, 0, prefix)
+    }
+
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        let units = self.utf32();
+        let suffix_len = suffix.chars().count();
+        suffix_len <= units.len() && matches_at(units, units.len() - suffix_len, suffix)
+    }
+
+    /// Returns the characters in `range` (character indices, not bytes) as a new string.
+    ///
+    /// # Panics
+    /// If `range` is out of bounds.
+    pub fn substr(&self, range: impl RangeBounds<usize>) -> GodotString {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        Self::from_utf32(&self.utf32()[bounds])
+    }
+
+    /// Splits the string at each occurrence of `delimiter`.
+    ///
+    /// Like GDScript's `split()` with default arguments, empty parts are included.
+    pub fn split(&self, delimiter: &str) -> StringArray {
+        let delimiter = GodotString::from(delimiter);
+        self.to_variant()
+            .call_builtin("split", &[delimiter.to_variant()])
+            .try_to::<StringArray>()
+            .expect("split() returns PackedStringArray")
+    }
+
+    pub fn to_lower(&self) -> GodotString {
+        self.call_returning_string("to_lower")
+    }
+
+    pub fn to_upper(&self) -> GodotString {
+        self.call_returning_string("to_upper")
+    }
+
+    /// Formats the string with placeholders like `%s` or `%d`, same as the `%` operator in GDScript.
+    ///
+    /// `args` holds a single value, or an `Array` for multiple placeholders.
+    /// Returns `None` if the placeholders don't match the arguments.
+    pub fn format(&self, args: &Variant) -> Option<GodotString> {
+        self.to_variant()
+            .evaluate(args, VariantOperator::Module)
+            .map(|result| result.try_to().expect("% operator returns String"))
+    }
+
+    /// Creates a string from UTF-16 code units, e.g. obtained from Windows APIs.
+    pub fn from_utf16(units: &[u16]) -> Self {
+        unsafe {
+            Self::from_string_sys_init(|string_ptr| {
+                let ctor = interface_fn!(string_new_with_utf16_chars_and_len);
+                ctor(string_ptr, units.as_ptr(), units.len() as i64);
+            })
+        }
+    }
+
+    /// Returns the string as UTF-16 code units.
+    pub fn to_utf16(&self) -> Vec<u16> {
+        unsafe {
+            let len = interface_fn!(string_to_utf16_chars)(self.string_sys(), ptr::null_mut(), 0);
+
+            assert!(len >= 0);
+            let mut buf = vec![0u16; len as usize];
+
+            interface_fn!(string_to_utf16_chars)(self.string_sys(), buf.as_mut_ptr(), len);
+            buf
+        }
+    }
+
+    fn from_utf32(units: &[u32]) -> Self {
+        unsafe {
+            Self::from_string_sys_init(|string_ptr| {
+                let ctor = interface_fn!(string_new_with_utf32_chars_and_len);
+                ctor(string_ptr, units.as_ptr(), units.len() as i64);
+            })
+        }
+    }
+
+    /// View of Godot's internal UTF-32 buffer (without null terminator).
+    fn utf32(&self) -> &[u32] {
+        let len = self.len();
+        if len == 0 {
+            // Empty strings have no buffer, and indexing would print an error
+            return &[];
+        }
+
+        unsafe {
+            let ptr = interface_fn!(string_operator_index_const)(self.string_sys(), 0);
+            slice::from_raw_parts(ptr, len)
+        }
+    }
+
+    fn call_returning_string(&self, method: &str) -> GodotString {
+        self.to_variant()
+            .call_builtin(method, &[])
+            .try_to()
+            .expect("method returns String")
+    }
+
     fn from_opaque(opaque: OpaqueString) -> Self {
         Self { opaque }
     }
@@ -78,6 +229,13 @@ impl_builtin_traits! {
     }
 }
 
+// Consistent with Eq, which compares code points
+impl Hash for GodotString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.utf32().hash(state)
+    }
+}
+
 impl From<&String> for GodotString {
     fn from(s: &String) -> GodotString {
         GodotString::from(s.as_str())
@@ -146,6 +304,46 @@ impl fmt::Debug for GodotString {
     }
 }
 
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Iterator over the characters of a [`GodotString`], created by [`GodotString::chars()`].
+#[derive(Clone)]
+pub struct Chars<'a> {
+    units: slice::Iter<'a, u32>,
+}
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.units.next().map(|&unit| to_char(unit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.units.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Chars<'_> {
+    fn next_back(&mut self) -> Option<char> {
+        self.units.next_back().map(|&unit| to_char(unit))
+    }
+}
+
+impl ExactSizeIterator for Chars<'_> {}
+
+/// Godot strings can contain unpaired surrogates, which are not valid `char`s.
+fn to_char(unit: u32) -> char {
+    char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Whether `what` occurs at character index `start`; caller ensures that it fits into `units`.
+fn matches_at(units: &[u32], start: usize, what: &str) -> bool {
+    what.chars()
+        .zip(&units[start..])
+        .all(|(c, &unit)| c as u32 == unit)
+}
+
 // While this is a nice optimisation for ptrcalls, it's not easily possible
 // to pass in &GodotString when doing varcalls.
 /*
@@ -6,11 +6,15 @@
 
 use crate::builtin::GodotString;
 use godot_ffi as sys;
-use sys::{ffi_methods, GodotFfi};
+use once_cell::sync::OnceCell;
+use sys::{ffi_methods, interface_fn, GodotFfi};
 
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::hash::{Hash, Hasher};
 
+/// Hash of the builtin method `StringName::hash()`, from the extension API JSON.
+const HASH_METHOD_HASH: i64 = 171192809;
+
 #[repr(C)]
 pub struct StringName {
     opaque: sys::types::OpaqueStringName,
@@ -21,6 +25,34 @@ impl StringName {
         Self { opaque }
     }
 
+    /// Godot's hash of this name, which is computed once when the name is interned.
+    fn godot_hash(&self) -> i64 {
+        static HASH_METHOD: OnceCell<sys::GDNativePtrBuiltInMethod> = OnceCell::new();
+
+        let method = HASH_METHOD.get_or_init(|| {
+            let method_name = StringName::from("hash");
+            unsafe {
+                interface_fn!(variant_get_ptr_builtin_method)(
+                    sys::GDNATIVE_VARIANT_TYPE_STRING_NAME,
+                    method_name.string_sys(),
+                    HASH_METHOD_HASH,
+                )
+            }
+        });
+        let method = method.expect("StringName::hash() not available");
+
+        let mut hash: i64 = 0;
+        unsafe {
+            method(self.sys(), std::ptr::null(), hash.sys_mut(), 0);
+        }
+        hash
+    }
+
+    /// Address of the interned data; identical for equal names, null for the empty name.
+    fn data_ptr(&self) -> usize {
+        unsafe { *(self.sys() as *const usize) }
+    }
+
     ffi_methods! {
         type sys::GDNativeStringNamePtr = *mut Opaque;
 
@@ -53,12 +85,26 @@ impl GodotFfi for StringName {
 impl_builtin_traits! {
     for StringName {
         Clone => string_name_construct_copy;
-        // Drop => string_name_destroy;
-        Eq => string_name_operator_equal;
+        Drop => string_name_destroy;
         Ord => string_name_operator_less;
     }
 }
 
+// C++ StringName is a single pointer to interned data
+sys::static_assert!(
+    std::mem::size_of::<sys::types::OpaqueStringName>() == std::mem::size_of::<usize>()
+);
+
+impl PartialEq for StringName {
+    fn eq(&self, other: &Self) -> bool {
+        // Names are interned, so comparing the data pointers is exact and needs no engine call.
+        // This is also what operator== does in C++.
+        self.data_ptr() == other.data_ptr()
+    }
+}
+
+impl Eq for StringName {}
+
 impl Default for StringName {
     fn default() -> Self {
         // Note: can't use from_sys_init(), as that calls the default constructor
@@ -95,10 +141,8 @@ impl Debug for StringName {
 
 impl Hash for StringName {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        // TODO use Godot hash via codegen
-        // C++: internal::gdn_interface->variant_get_ptr_builtin_method(GDNATIVE_VARIANT_TYPE_STRING_NAME, "hash", 171192809);
-
-        self.to_string().hash(state)
+        // Precomputed by Godot, so no string needs to be converted or traversed
+        self.godot_hash().hash(state)
     }
 }
 
@@ -132,3 +176,55 @@ impl From<&StringName> for GodotString {
         }
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Lazily initialized `StringName` stored in a static; used by [`string_name!`].
+#[doc(hidden)]
+pub struct StaticStringName {
+    name: &'static str,
+    cell: OnceCell<StringName>,
+}
+
+// SAFETY: the contained StringName is never mutated after initialization, and Godot's reference counting
+// of interned names is thread-safe.
+unsafe impl Sync for StaticStringName {}
+unsafe impl Send for StaticStringName {}
+
+impl StaticStringName {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            cell: OnceCell::new(),
+        }
+    }
+
+    pub fn get(&'static self) -> &'static StringName {
+        self.cell.get_or_init(|| StringName::from(self.name))
+    }
+}
+
+/// Returns a `&'static StringName` for a string literal, interned once per call site.
+///
+/// The first evaluation constructs the name; subsequent ones are a cheap lookup. Useful for names
+/// which are passed to the engine frequently, for example in `Object::call()` every frame.
+///
+/// ```no_run
+/// use godot::builtin::string_name;
+/// use godot::engine::Node;
+/// # fn process(mut node: godot::obj::Gd<Node>) {
+/// node.call(string_name!("update_score").clone(), &[]);
+/// # }
+/// ```
+///
+/// The engine must be initialized at the time of the first evaluation. Static names are never destroyed.
+#[macro_export]
+macro_rules! string_name {
+    ($name:literal) => {{
+        static NAME: $crate::builtin::StaticStringName =
+            $crate::builtin::StaticStringName::new($name);
+        NAME.get()
+    }};
+}
+
+pub use crate::string_name;
@@ -0,0 +1,1025 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Godot's binary `Variant` format, as produced by GDScript's `var_to_bytes()`.
+//!
+//! The [`Value`] tree mirrors [`VariantType`] and can be encoded/decoded in pure Rust, without a running engine.
+//! This makes it possible to read save games or network packets in standalone tools.
+//!
+//! When the engine is available, `Value` can additionally be converted from/to [`Variant`] through the
+//! [`ToVariant`] and [`FromVariant`] traits.
+//!
+//! Notes on the format (see `core/io/marshalls.cpp` in Godot):
+//! * Everything is little-endian and aligned to 4 bytes.
+//! * Each value starts with a `u32` header: the variant type in the lowest byte, plus flags.
+//! * Integers and floats are stored as 32 bits if lossless, and as 64 bits otherwise.
+//! * Math types store `real_t` components. 64-bit reals (from double-precision builds) are accepted when decoding,
+//!   but narrowed to `f32`, just like a single-precision engine would do.
+//! * Objects are only supported as instance IDs (the default of `var_to_bytes()`); full object serialization
+//!   (`var_to_bytes_with_objects()`) is rejected, apart from `null` objects.
+
+use crate::builtin::{ByteArray, FromVariant, ToVariant, Variant, VariantConversionError};
+use crate::obj::InstanceId;
+use godot_ffi as sys;
+use std::fmt;
+use sys::{interface_fn, GodotFfi, VariantType};
+
+/// Lower byte of header holds the variant type.
+const HEADER_TYPE_MASK: u32 = 0xFF;
+
+/// Integers, floats and `real_t` components are stored with 64 bits.
+const ENCODE_FLAG_64: u32 = 1 << 16;
+
+/// Objects are stored as instance IDs (shares the bit with `ENCODE_FLAG_64`, which is meaningless for objects).
+const ENCODE_FLAG_OBJECT_AS_ID: u32 = 1 << 16;
+
+/// Arrays and dictionaries store a "shared" flag in the highest bit of their size.
+const CONTAINER_SIZE_MASK: u32 = 0x7FFF_FFFF;
+
+/// Node paths use the highest bit of the name count to mark the (current) new format.
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+const NODE_PATH_ABSOLUTE: u32 = 1;
+const NODE_PATH_PROPERTY: u32 = 2;
+
+/// Same limit as `Variant::MAX_RECURSION_DEPTH` in Godot.
+const MAX_RECURSION_DEPTH: usize = 1024;
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Value tree
+
+/// Engine-independent representation of a `Variant`, with one enumerator per [`VariantType`].
+///
+/// Vectors and matrices are stored as plain arrays of their components, in the order Godot serializes them.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+
+    Vector2([f32; 2]),
+    Vector2i([i32; 2]),
+    Rect2 {
+        position: [f32; 2],
+        size: [f32; 2],
+    },
+    Rect2i {
+        position: [i32; 2],
+        size: [i32; 2],
+    },
+    Vector3([f32; 3]),
+    Vector3i([i32; 3]),
+    /// Columns `x`, `y` and `origin`.
+    Transform2D([[f32; 2]; 3]),
+    Vector4([f32; 4]),
+    Vector4i([i32; 4]),
+    Plane {
+        normal: [f32; 3],
+        d: f32,
+    },
+    /// Components `x`, `y`, `z`, `w`.
+    Quaternion([f32; 4]),
+    AABB {
+        position: [f32; 3],
+        size: [f32; 3],
+    },
+    /// Rows of the 3x3 matrix.
+    Basis([[f32; 3]; 3]),
+    /// Rows of the basis, followed by the origin.
+    Transform3D {
+        basis: [[f32; 3]; 3],
+        origin: [f32; 3],
+    },
+    /// Columns of the 4x4 matrix.
+    Projection([[f32; 4]; 4]),
+
+    /// Components `r`, `g`, `b`, `a`.
+    Color([f32; 4]),
+    StringName(String),
+    NodePath {
+        names: Vec<String>,
+        subnames: Vec<String>,
+        absolute: bool,
+    },
+    Rid(u64),
+    /// Object referenced by instance ID; `None` for a null object.
+    Object(Option<InstanceId>),
+    /// Callables carry no data in the binary format; they always decode to a null callable.
+    Callable,
+    Signal {
+        name: String,
+        object: Option<InstanceId>,
+    },
+    /// Key-value pairs, in insertion order.
+    Dictionary(Vec<(Value, Value)>),
+    Array(Vec<Value>),
+
+    PackedByteArray(Vec<u8>),
+    PackedInt32Array(Vec<i32>),
+    PackedInt64Array(Vec<i64>),
+    PackedFloat32Array(Vec<f32>),
+    PackedFloat64Array(Vec<f64>),
+    PackedStringArray(Vec<String>),
+    PackedVector2Array(Vec<[f32; 2]>),
+    PackedVector3Array(Vec<[f32; 3]>),
+    PackedColorArray(Vec<[f32; 4]>),
+}
+
+impl Value {
+    /// The variant type which this value represents.
+    pub fn variant_type(&self) -> VariantType {
+        match self {
+            Value::Nil => VariantType::Nil,
+            Value::Bool(_) => VariantType::Bool,
+            Value::Int(_) => VariantType::Int,
+            Value::Float(_) => VariantType::Float,
+            Value::String(_) => VariantType::String,
+            Value::Vector2(_) => VariantType::Vector2,
+            Value::Vector2i(_) => VariantType::Vector2i,
+            Value::Rect2 { .. } => VariantType::Rect2,
+            Value::Rect2i { .. } => VariantType::Rect2i,
+            Value::Vector3(_) => VariantType::Vector3,
+            Value::Vector3i(_) => VariantType::Vector3i,
+            Value::Transform2D(_) => VariantType::Transform2D,
+            Value::Vector4(_) => VariantType::Vector4,
+            Value::Vector4i(_) => VariantType::Vector4i,
+            Value::Plane { .. } => VariantType::Plane,
+            Value::Quaternion(_) => VariantType::Quaternion,
+            Value::AABB { .. } => VariantType::AABB,
+            Value::Basis(_) => VariantType::Basis,
+            Value::Transform3D { .. } => VariantType::Transform3D,
+            Value::Projection(_) => VariantType::Projection,
+            Value::Color(_) => VariantType::Color,
+            Value::StringName(_) => VariantType::StringName,
+            Value::NodePath { .. } => VariantType::NodePath,
+            Value::Rid(_) => VariantType::RID,
+            Value::Object(_) => VariantType::Object,
+            Value::Callable => VariantType::Callable,
+            Value::Signal { .. } => VariantType::Signal,
+            Value::Dictionary(_) => VariantType::Dictionary,
+            Value::Array(_) => VariantType::Array,
+            Value::PackedByteArray(_) => VariantType::PackedByteArray,
+            Value::PackedInt32Array(_) => VariantType::PackedInt32Array,
+            Value::PackedInt64Array(_) => VariantType::PackedInt64Array,
+            Value::PackedFloat32Array(_) => VariantType::PackedFloat32Array,
+            Value::PackedFloat64Array(_) => VariantType::PackedFloat64Array,
+            Value::PackedStringArray(_) => VariantType::PackedStringArray,
+            Value::PackedVector2Array(_) => VariantType::PackedVector2Array,
+            Value::PackedVector3Array(_) => VariantType::PackedVector3Array,
+            Value::PackedColorArray(_) => VariantType::PackedColorArray,
+        }
+    }
+
+    /// Encodes this value in the same way as GDScript's `var_to_bytes()`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(self)
+    }
+
+    /// Decodes a value in the same way as GDScript's `bytes_to_var()`.
+    ///
+    /// Like in Godot, trailing bytes after the value are ignored; use [`decode()`] to know how many bytes were read.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        decode(bytes).map(|(value, _len)| value)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Errors
+
+/// Error which occurs when bytes cannot be decoded into a [`Value`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// Input ended before the value was complete.
+    UnexpectedEnd,
+
+    /// Header holds a type which is not a valid `VariantType`.
+    InvalidType(u32),
+
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+
+    /// Sizes or flags are inconsistent.
+    InvalidData(&'static str),
+
+    /// Arrays or dictionaries are nested too deeply.
+    RecursionLimit,
+
+    /// Objects serialized with `var_to_bytes_with_objects()` cannot be decoded outside the engine.
+    FullObject { class_name: String },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeError::InvalidType(ty) => write!(f, "invalid variant type {ty}"),
+            DecodeError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            DecodeError::InvalidData(what) => write!(f, "invalid data: {what}"),
+            DecodeError::RecursionLimit => {
+                write!(f, "max recursion depth ({MAX_RECURSION_DEPTH}) exceeded")
+            }
+            DecodeError::FullObject { class_name } => {
+                write!(f, "cannot decode full object of class `{class_name}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Encoding
+
+/// Encodes `value` in the same way as GDScript's `var_to_bytes()`.
+pub fn encode(value: &Value) -> Vec<u8> {
+    let mut encoder = Encoder { buf: Vec::new() };
+    encoder.value(value);
+    encoder.buf
+}
+
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn value(&mut self, value: &Value) {
+        let ty = value.variant_type().sys() as u32;
+
+        match value {
+            Value::Nil => self.u32(ty),
+            Value::Bool(b) => {
+                self.u32(ty);
+                self.u32(*b as u32);
+            }
+            Value::Int(i) => {
+                if i32::try_from(*i).is_ok() {
+                    self.u32(ty);
+                    self.i32(*i as i32);
+                } else {
+                    self.u32(ty | ENCODE_FLAG_64);
+                    self.i64(*i);
+                }
+            }
+            Value::Float(f) => {
+                // Same check as Godot; also causes NaN to be stored with 64 bits
+                if (*f as f32) as f64 == *f {
+                    self.u32(ty);
+                    self.f32(*f as f32);
+                } else {
+                    self.u32(ty | ENCODE_FLAG_64);
+                    self.f64(*f);
+                }
+            }
+            Value::String(s) | Value::StringName(s) => {
+                self.u32(ty);
+                self.string(s);
+            }
+            Value::Vector2(v) => {
+                self.u32(ty);
+                self.reals(v);
+            }
+            Value::Vector2i(v) => {
+                self.u32(ty);
+                self.ints(v);
+            }
+            Value::Rect2 { position, size } => {
+                self.u32(ty);
+                self.reals(position);
+                self.reals(size);
+            }
+            Value::Rect2i { position, size } => {
+                self.u32(ty);
+                self.ints(position);
+                self.ints(size);
+            }
+            Value::Vector3(v) => {
+                self.u32(ty);
+                self.reals(v);
+            }
+            Value::Vector3i(v) => {
+                self.u32(ty);
+                self.ints(v);
+            }
+            Value::Transform2D(columns) => {
+                self.u32(ty);
+                columns.iter().for_each(|c| self.reals(c));
+            }
+            Value::Vector4(v) => {
+                self.u32(ty);
+                self.reals(v);
+            }
+            Value::Vector4i(v) => {
+                self.u32(ty);
+                self.ints(v);
+            }
+            Value::Plane { normal, d } => {
+                self.u32(ty);
+                self.reals(normal);
+                self.f32(*d);
+            }
+            Value::Quaternion(q) => {
+                self.u32(ty);
+                self.reals(q);
+            }
+            Value::AABB { position, size } => {
+                self.u32(ty);
+                self.reals(position);
+                self.reals(size);
+            }
+            Value::Basis(rows) => {
+                self.u32(ty);
+                rows.iter().for_each(|r| self.reals(r));
+            }
+            Value::Transform3D { basis, origin } => {
+                self.u32(ty);
+                basis.iter().for_each(|r| self.reals(r));
+                self.reals(origin);
+            }
+            Value::Projection(columns) => {
+                self.u32(ty);
+                columns.iter().for_each(|c| self.reals(c));
+            }
+            Value::Color(c) => {
+                self.u32(ty);
+                self.reals(c);
+            }
+            Value::NodePath {
+                names,
+                subnames,
+                absolute,
+            } => {
+                self.u32(ty);
+                self.u32(names.len() as u32 | NODE_PATH_NEW_FORMAT);
+                self.u32(subnames.len() as u32);
+                self.u32(if *absolute { NODE_PATH_ABSOLUTE } else { 0 });
+                names.iter().chain(subnames).for_each(|s| self.string(s));
+            }
+            Value::Rid(id) => {
+                self.u32(ty);
+                self.u64(*id);
+            }
+            Value::Object(id) => {
+                self.u32(ty | ENCODE_FLAG_OBJECT_AS_ID);
+                self.u64(id.map_or(0, InstanceId::to_u64));
+            }
+            Value::Callable => self.u32(ty),
+            Value::Signal { name, object } => {
+                self.u32(ty);
+                self.string(name);
+                self.u64(object.map_or(0, InstanceId::to_u64));
+            }
+            Value::Dictionary(pairs) => {
+                self.u32(ty);
+                self.len(pairs.len());
+                for (key, value) in pairs {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            Value::Array(elements) => {
+                self.u32(ty);
+                self.len(elements.len());
+                elements.iter().for_each(|e| self.value(e));
+            }
+            Value::PackedByteArray(bytes) => {
+                self.u32(ty);
+                self.len(bytes.len());
+                self.buf.extend_from_slice(bytes);
+                self.pad();
+            }
+            Value::PackedInt32Array(ints) => {
+                self.u32(ty);
+                self.len(ints.len());
+                ints.iter().for_each(|&i| self.i32(i));
+            }
+            Value::PackedInt64Array(ints) => {
+                self.u32(ty);
+                self.len(ints.len());
+                ints.iter().for_each(|&i| self.i64(i));
+            }
+            Value::PackedFloat32Array(floats) => {
+                self.u32(ty);
+                self.len(floats.len());
+                floats.iter().for_each(|&f| self.f32(f));
+            }
+            Value::PackedFloat64Array(floats) => {
+                self.u32(ty);
+                self.len(floats.len());
+                floats.iter().for_each(|&f| self.f64(f));
+            }
+            Value::PackedStringArray(strings) => {
+                self.u32(ty);
+                self.len(strings.len());
+                strings.iter().for_each(|s| self.string(s));
+            }
+            Value::PackedVector2Array(vectors) => {
+                self.u32(ty);
+                self.len(vectors.len());
+                vectors.iter().for_each(|v| self.reals(v));
+            }
+            Value::PackedVector3Array(vectors) => {
+                self.u32(ty);
+                self.len(vectors.len());
+                vectors.iter().for_each(|v| self.reals(v));
+            }
+            Value::PackedColorArray(colors) => {
+                self.u32(ty);
+                self.len(colors.len());
+                colors.iter().for_each(|c| self.reals(c));
+            }
+        }
+    }
+
+    fn len(&mut self, len: usize) {
+        assert!(
+            len <= CONTAINER_SIZE_MASK as usize,
+            "container with {len} elements is too large to be encoded"
+        );
+        self.u32(len as u32);
+    }
+
+    fn string(&mut self, s: &str) {
+        self.len(s.len());
+        self.buf.extend_from_slice(s.as_bytes());
+        self.pad();
+    }
+
+    fn pad(&mut self) {
+        while self.buf.len() % 4 != 0 {
+            self.buf.push(0);
+        }
+    }
+
+    fn reals(&mut self, components: &[f32]) {
+        components.iter().for_each(|&c| self.f32(c));
+    }
+
+    fn ints(&mut self, components: &[i32]) {
+        components.iter().for_each(|&c| self.i32(c));
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Decoding
+
+/// Decodes a value in the same way as GDScript's `bytes_to_var()`.
+///
+/// Returns the value together with the number of bytes read.
+pub fn decode(bytes: &[u8]) -> Result<(Value, usize), DecodeError> {
+    let mut decoder = Decoder { bytes, pos: 0 };
+    let value = decoder.value(0)?;
+    Ok((value, decoder.pos))
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn value(&mut self, depth: usize) -> Result<Value, DecodeError> {
+        if depth > MAX_RECURSION_DEPTH {
+            return Err(DecodeError::RecursionLimit);
+        }
+
+        let header = self.u32()?;
+        let flags = header & !HEADER_TYPE_MASK;
+        let is_64 = flags & ENCODE_FLAG_64 != 0;
+
+        let ty = header & HEADER_TYPE_MASK;
+        if ty >= sys::GDNATIVE_VARIANT_TYPE_VARIANT_MAX as u32 {
+            return Err(DecodeError::InvalidType(ty));
+        }
+
+        let value = match VariantType::from_sys(ty as sys::GDNativeVariantType) {
+            VariantType::Nil => Value::Nil,
+            VariantType::Bool => Value::Bool(self.u32()? != 0),
+            VariantType::Int => {
+                if is_64 {
+                    Value::Int(self.i64()?)
+                } else {
+                    Value::Int(self.i32()? as i64)
+                }
+            }
+            VariantType::Float => {
+                if is_64 {
+                    Value::Float(self.f64()?)
+                } else {
+                    Value::Float(self.f32()? as f64)
+                }
+            }
+            VariantType::String => Value::String(self.string()?),
+            VariantType::Vector2 => Value::Vector2(self.reals(is_64)?),
+            VariantType::Vector2i => Value::Vector2i(self.ints()?),
+            VariantType::Rect2 => Value::Rect2 {
+                position: self.reals(is_64)?,
+                size: self.reals(is_64)?,
+            },
+            VariantType::Rect2i => Value::Rect2i {
+                position: self.ints()?,
+                size: self.ints()?,
+            },
+            VariantType::Vector3 => Value::Vector3(self.reals(is_64)?),
+            VariantType::Vector3i => Value::Vector3i(self.ints()?),
+            VariantType::Transform2D => {
+                Value::Transform2D([self.reals(is_64)?, self.reals(is_64)?, self.reals(is_64)?])
+            }
+            VariantType::Vector4 => Value::Vector4(self.reals(is_64)?),
+            VariantType::Vector4i => Value::Vector4i(self.ints()?),
+            VariantType::Plane => Value::Plane {
+                normal: self.reals(is_64)?,
+                d: self.real(is_64)?,
+            },
+            VariantType::Quaternion => Value::Quaternion(self.reals(is_64)?),
+            VariantType::AABB => Value::AABB {
+                position: self.reals(is_64)?,
+                size: self.reals(is_64)?,
+            },
+            VariantType::Basis => {
+                Value::Basis([self.reals(is_64)?, self.reals(is_64)?, self.reals(is_64)?])
+            }
+            VariantType::Transform3D => Value::Transform3D {
+                basis: [self.reals(is_64)?, self.reals(is_64)?, self.reals(is_64)?],
+                origin: self.reals(is_64)?,
+            },
+            VariantType::Projection => Value::Projection([
+                self.reals(is_64)?,
+                self.reals(is_64)?,
+                self.reals(is_64)?,
+                self.reals(is_64)?,
+            ]),
+            // Color is always stored with 32-bit floats, independent of real_t
+            VariantType::Color => Value::Color(self.reals(false)?),
+            VariantType::StringName => Value::StringName(self.string()?),
+            VariantType::NodePath => self.node_path()?,
+            VariantType::RID => Value::Rid(self.u64()?),
+            VariantType::Object => {
+                if flags & ENCODE_FLAG_OBJECT_AS_ID != 0 {
+                    Value::Object(InstanceId::try_from_u64(self.u64()?))
+                } else {
+                    // Full object: class name, followed by properties. Only null objects are supported.
+                    let class_name = self.string()?;
+                    if !class_name.is_empty() {
+                        return Err(DecodeError::FullObject { class_name });
+                    }
+                    Value::Object(None)
+                }
+            }
+            VariantType::Callable => Value::Callable,
+            VariantType::Signal => Value::Signal {
+                name: self.string()?,
+                object: InstanceId::try_from_u64(self.u64()?),
+            },
+            VariantType::Dictionary => {
+                let len = self.len()?;
+                let mut pairs = Vec::new();
+                for _ in 0..len {
+                    let key = self.value(depth + 1)?;
+                    let value = self.value(depth + 1)?;
+                    pairs.push((key, value));
+                }
+                Value::Dictionary(pairs)
+            }
+            VariantType::Array => {
+                let len = self.len()?;
+                let mut elements = Vec::new();
+                for _ in 0..len {
+                    elements.push(self.value(depth + 1)?);
+                }
+                Value::Array(elements)
+            }
+            VariantType::PackedByteArray => {
+                let len = self.len()?;
+                let bytes = self.take(len)?.to_vec();
+                self.skip_padding(len)?;
+                Value::PackedByteArray(bytes)
+            }
+            VariantType::PackedInt32Array => Value::PackedInt32Array(self.packed(4, Self::i32)?),
+            VariantType::PackedInt64Array => Value::PackedInt64Array(self.packed(8, Self::i64)?),
+            VariantType::PackedFloat32Array => {
+                Value::PackedFloat32Array(self.packed(4, Self::f32)?)
+            }
+            VariantType::PackedFloat64Array => {
+                Value::PackedFloat64Array(self.packed(8, Self::f64)?)
+            }
+            VariantType::PackedStringArray => {
+                // Strings have variable length, so don't use packed()
+                let len = self.len()?;
+                let mut strings = Vec::new();
+                for _ in 0..len {
+                    strings.push(self.string()?);
+                }
+                Value::PackedStringArray(strings)
+            }
+            VariantType::PackedVector2Array => {
+                let size = if is_64 { 16 } else { 8 };
+                Value::PackedVector2Array(self.packed(size, |d| d.reals(is_64))?)
+            }
+            VariantType::PackedVector3Array => {
+                let size = if is_64 { 24 } else { 12 };
+                Value::PackedVector3Array(self.packed(size, |d| d.reals(is_64))?)
+            }
+            VariantType::PackedColorArray => {
+                Value::PackedColorArray(self.packed(16, |d| d.reals(false))?)
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn node_path(&mut self) -> Result<Value, DecodeError> {
+        let name_count = self.u32()?;
+        if name_count & NODE_PATH_NEW_FORMAT == 0 {
+            return Err(DecodeError::InvalidData("node path in old format"));
+        }
+
+        let name_count = (name_count & !NODE_PATH_NEW_FORMAT) as usize;
+        let mut subname_count = self.u32()? as usize;
+        let flags = self.u32()?;
+
+        // Old "property" flag; the property is stored as an additional subname
+        if flags & NODE_PATH_PROPERTY != 0 {
+            subname_count += 1;
+        }
+
+        let mut names = Vec::new();
+        for _ in 0..name_count {
+            names.push(self.string()?);
+        }
+
+        let mut subnames = Vec::new();
+        for _ in 0..subname_count {
+            subnames.push(self.string()?);
+        }
+
+        Ok(Value::NodePath {
+            names,
+            subnames,
+            absolute: flags & NODE_PATH_ABSOLUTE != 0,
+        })
+    }
+
+    /// Reads a packed array of elements with fixed size, after checking that the input is long enough.
+    fn packed<T>(
+        &mut self,
+        elem_size: usize,
+        read_elem: impl Fn(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<Vec<T>, DecodeError> {
+        let len = self.len()?;
+
+        // Check up front, to not allocate huge vectors for corrupt sizes
+        let byte_len = len
+            .checked_mul(elem_size)
+            .ok_or(DecodeError::InvalidData("packed array size overflows"))?;
+        if self.remaining() < byte_len {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let mut elements = Vec::with_capacity(len);
+        for _ in 0..len {
+            elements.push(read_elem(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn len(&mut self) -> Result<usize, DecodeError> {
+        Ok((self.u32()? & CONTAINER_SIZE_MASK) as usize)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        let string = std::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::InvalidUtf8)?
+            .to_string();
+
+        self.skip_padding(len)?;
+        Ok(string)
+    }
+
+    fn skip_padding(&mut self, len: usize) -> Result<(), DecodeError> {
+        if len % 4 != 0 {
+            self.take(4 - len % 4)?;
+        }
+        Ok(())
+    }
+
+    fn real(&mut self, is_64: bool) -> Result<f32, DecodeError> {
+        if is_64 {
+            Ok(self.f64()? as f32)
+        } else {
+            self.f32()
+        }
+    }
+
+    fn reals<const N: usize>(&mut self, is_64: bool) -> Result<[f32; N], DecodeError> {
+        let mut components = [0.0; N];
+        for c in components.iter_mut() {
+            *c = self.real(is_64)?;
+        }
+        Ok(components)
+    }
+
+    fn ints<const N: usize>(&mut self) -> Result<[i32; N], DecodeError> {
+        let mut components = [0; N];
+        for c in components.iter_mut() {
+            *c = self.i32()?;
+        }
+        Ok(components)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, DecodeError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, DecodeError> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let bytes = self.take(N)?;
+        Ok(bytes.try_into().expect("slice has length N"))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.remaining() < len {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Engine conversions
+
+// Conversions go through Godot's own bytes_to_var()/var_to_bytes(), so every variant type is supported
+// without needing a Rust counterpart for each builtin.
+
+impl ToVariant for Value {
+    fn to_variant(&self) -> Variant {
+        let bytes = byte_array_from_slice(&self.to_bytes());
+        let variant = crate::engine::utilities::bytes_to_var(bytes);
+
+        // The engine copies the argument, so the local array is still owned here
+        unsafe { destroy_byte_array(bytes) };
+        variant
+    }
+}
+
+impl FromVariant for Value {
+    fn try_from_variant(variant: &Variant) -> Result<Self, VariantConversionError> {
+        let bytes = crate::engine::utilities::var_to_bytes(variant.clone());
+        let bytes = byte_array_into_vec(bytes);
+
+        Value::from_bytes(&bytes).map_err(|_| VariantConversionError)
+    }
+}
+
+fn byte_array_from_slice(bytes: &[u8]) -> ByteArray {
+    let mut variant = unsafe {
+        let empty = ByteArray::from_sys_init(|ptr| {
+            let ctor = sys::method_table().packed_byte_array_construct_default;
+            ctor(ptr, std::ptr::null());
+        });
+
+        let variant = Variant::from_var_sys_init(|variant_ptr| {
+            let converter = sys::method_table().packed_byte_array_to_variant;
+            converter(variant_ptr, empty.sys());
+        });
+        destroy_byte_array(empty);
+        variant
+    };
+
+    variant.call_builtin("resize", &[(bytes.len() as i64).to_variant()]);
+
+    unsafe {
+        let mut array = ByteArray::from_sys_init(|ptr| {
+            let ctor = sys::method_table().packed_byte_array_construct_default;
+            ctor(ptr, std::ptr::null());
+        });
+
+        let converter = sys::method_table().packed_byte_array_from_variant;
+        converter(array.sys_mut(), variant.var_sys());
+
+        if !bytes.is_empty() {
+            // Non-const index triggers copy-on-write, so `variant` is not affected
+            let dst = interface_fn!(packed_byte_array_operator_index)(array.sys(), 0);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+        }
+
+        array
+    }
+}
+
+fn byte_array_into_vec(array: ByteArray) -> Vec<u8> {
+    let mut variant = unsafe {
+        Variant::from_var_sys_init(|variant_ptr| {
+            let converter = sys::method_table().packed_byte_array_to_variant;
+            converter(variant_ptr, array.sys());
+        })
+    };
+
+    let len = variant
+        .call_builtin("size", &[])
+        .try_to::<i64>()
+        .expect("size() returns int") as usize;
+
+    let bytes = if len == 0 {
+        Vec::new()
+    } else {
+        unsafe {
+            let src = interface_fn!(packed_byte_array_operator_index_const)(array.sys(), 0);
+            std::slice::from_raw_parts(src, len).to_vec()
+        }
+    };
+
+    unsafe { destroy_byte_array(array) };
+    bytes
+}
+
+/// Packed arrays are currently `Copy` stubs without destructor, so the memory is released manually.
+unsafe fn destroy_byte_array(mut array: ByteArray) {
+    let destructor = sys::method_table().packed_byte_array_destroy;
+    destructor(array.sys_mut());
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(value: Value) {
+        let bytes = value.to_bytes();
+        assert_eq!(bytes.len() % 4, 0, "{value:?} not aligned");
+
+        let (back, len) = decode(&bytes).expect("decode");
+        assert_eq!(back, value);
+        assert_eq!(len, bytes.len());
+    }
+
+    #[test]
+    fn encode_scalars() {
+        assert_eq!(encode(&Value::Nil), [0, 0, 0, 0]);
+        assert_eq!(encode(&Value::Bool(true)), [1, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(
+            encode(&Value::Int(-2)),
+            [2, 0, 0, 0, 0xFE, 0xFF, 0xFF, 0xFF]
+        );
+
+        // 64-bit flag
+        let bytes = encode(&Value::Int(1 << 40));
+        assert_eq!(&bytes[..4], [2, 0, 1, 0]);
+        assert_eq!(bytes.len(), 12);
+
+        let bytes = encode(&Value::Float(0.5));
+        assert_eq!(bytes, [3, 0, 0, 0, 0, 0, 0, 0x3F]);
+        let bytes = encode(&Value::Float(0.1));
+        assert_eq!(&bytes[..4], [3, 0, 1, 0]);
+        assert_eq!(bytes.len(), 12);
+    }
+
+    #[test]
+    fn encode_string_padding() {
+        let bytes = encode(&Value::String("abcde".to_string()));
+        assert_eq!(
+            bytes,
+            [4, 0, 0, 0, 5, 0, 0, 0, b'a', b'b', b'c', b'd', b'e', 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn roundtrip_values() {
+        roundtrip(Value::Int(i64::MIN));
+        roundtrip(Value::Float(-1.0e300));
+        roundtrip(Value::String("ünïcödé".to_string()));
+        roundtrip(Value::StringName("name".to_string()));
+        roundtrip(Value::Vector2i([i32::MIN, i32::MAX]));
+        roundtrip(Value::Rect2 {
+            position: [1.0, 2.0],
+            size: [3.0, 4.5],
+        });
+        roundtrip(Value::Transform3D {
+            basis: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            origin: [7.0, 8.0, 9.0],
+        });
+        roundtrip(Value::Color([0.1, 0.2, 0.3, 1.0]));
+        roundtrip(Value::NodePath {
+            names: vec!["root".to_string(), "Player".to_string()],
+            subnames: vec!["position".to_string()],
+            absolute: true,
+        });
+        roundtrip(Value::Object(None));
+        roundtrip(Value::Object(InstanceId::try_from_i64(1234)));
+        roundtrip(Value::Signal {
+            name: "hit".to_string(),
+            object: None,
+        });
+        roundtrip(Value::Dictionary(vec![
+            (Value::String("key".to_string()), Value::Int(1)),
+            (
+                Value::Int(2),
+                Value::Array(vec![Value::Nil, Value::Bool(false)]),
+            ),
+        ]));
+        roundtrip(Value::PackedByteArray(vec![1, 2, 3]));
+        roundtrip(Value::PackedInt64Array(vec![-1, i64::MAX]));
+        roundtrip(Value::PackedFloat64Array(vec![0.1, 0.2]));
+        roundtrip(Value::PackedStringArray(vec![
+            "a".to_string(),
+            "".to_string(),
+        ]));
+        roundtrip(Value::PackedVector3Array(vec![[1.0, 2.0, 3.0]]));
+        roundtrip(Value::PackedColorArray(vec![[0.0, 0.5, 1.0, 1.0]]));
+    }
+
+    #[test]
+    fn decode_64bit_reals() {
+        let mut bytes = vec![];
+        bytes
+            .extend_from_slice(&(VariantType::Vector2.sys() as u32 | ENCODE_FLAG_64).to_le_bytes());
+        bytes.extend_from_slice(&1.5f64.to_le_bytes());
+        bytes.extend_from_slice(&(-2.25f64).to_le_bytes());
+
+        assert_eq!(Value::from_bytes(&bytes), Ok(Value::Vector2([1.5, -2.25])));
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(decode(&[2, 0, 0]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode(&[200, 0, 0, 0]), Err(DecodeError::InvalidType(200)));
+
+        // Array claims more elements than present
+        assert_eq!(
+            decode(&[28, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+
+        // Packed array with huge size must not allocate
+        assert_eq!(
+            decode(&[31, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0x7F]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn decode_recursion_limit() {
+        let mut bytes = vec![];
+        for _ in 0..=MAX_RECURSION_DEPTH + 1 {
+            bytes.extend_from_slice(&[28, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        assert_eq!(decode(&bytes), Err(DecodeError::RecursionLimit));
+    }
+}
@@ -137,8 +137,16 @@ mod impls {
     impl_variant_traits!(Vector2i, vector2i_to_variant, vector2i_from_variant, Vector2i);
     impl_variant_traits!(Vector3i, vector3i_to_variant, vector3i_from_variant, Vector3i);
     impl_variant_traits!(Color, color_to_variant, color_from_variant, Color);
+    impl_variant_traits!(Rect2, rect2_to_variant, rect2_from_variant, Rect2);
+    impl_variant_traits!(Quaternion, quaternion_to_variant, quaternion_from_variant, Quaternion);
+    impl_variant_traits!(Transform2D, transform2d_to_variant, transform2d_from_variant, Transform2D);
+    impl_variant_traits!(Transform3D, transform3d_to_variant, transform3d_from_variant, Transform3D);
     impl_variant_traits!(GodotString, string_to_variant, string_from_variant, String);
     impl_variant_traits!(StringName, string_name_to_variant, string_name_from_variant, StringName);
+    impl_variant_traits!(NodePath, node_path_to_variant, node_path_from_variant, NodePath);
+    impl_variant_traits!(Array, array_to_variant, array_from_variant, Array);
+    impl_variant_traits!(Dictionary, dictionary_to_variant, dictionary_from_variant, Dictionary);
+    impl_variant_traits!(StringArray, packed_string_array_to_variant, packed_string_array_from_variant, PackedStringArray);
 
 
     impl_variant_traits!(i64, int_to_variant, int_from_variant, Int, GDNATIVE_EXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT64);
@@ -4,13 +4,15 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use crate::builtin::GodotString;
+use crate::builtin::{GodotString, StringName};
 use godot_ffi as sys;
 use godot_ffi::GodotFfi;
 use std::{fmt, ptr};
 use sys::types::OpaqueVariant;
 use sys::{ffi_methods, interface_fn};
 
+pub mod encoding;
+
 mod impls;
 mod variant_traits;
 
@@ -96,6 +98,36 @@ impl Variant {
         }
     }
 
+    /// Calls a method of the contained builtin type by name, panicking on failure.
+    ///
+    /// Methods which mutate the value (e.g. `resize()` on packed arrays) operate on the value inside this variant.
+    pub(crate) fn call_builtin(&mut self, method: &str, args: &[Variant]) -> Variant {
+        let method_name = StringName::from(method);
+        let args_sys: Vec<_> = args.iter().map(|arg| arg.var_sys()).collect();
+        let mut error = sys::default_call_error();
+
+        let result = unsafe {
+            Variant::from_var_sys_init(|return_ptr| {
+                interface_fn!(variant_call)(
+                    self.var_sys(),
+                    method_name.string_sys(),
+                    args_sys.as_ptr(),
+                    args_sys.len() as i64,
+                    return_ptr,
+                    ptr::addr_of_mut!(error),
+                )
+            })
+        };
+
+        assert_eq!(
+            error.error,
+            sys::GDNATIVE_CALL_OK,
+            "failed to call {method}() on {:?}",
+            self.get_type()
+        );
+        result
+    }
+
     pub(crate) fn sys_type(&self) -> sys::GDNativeVariantType {
         unsafe {
             let ty: sys::GDNativeVariantType = interface_fn!(variant_get_type)(self.var_sys());
@@ -11,6 +11,11 @@ type Inner = glam::f32::Vec2;
 //type Inner = glam::f64::DVec2;
 
 #[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(C)]
 pub struct Vector2 {
     inner: Inner,
@@ -59,6 +64,11 @@ impl std::fmt::Display for Vector2 {
 type IInner = glam::IVec2;
 
 #[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(C)]
 pub struct Vector2i {
     inner: IInner,
@@ -11,6 +11,11 @@ type Inner = glam::f32::Vec3;
 // type Inner = glam::f64::DVec3;
 
 #[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(C)]
 pub struct Vector3 {
     inner: Inner,
@@ -41,6 +46,11 @@ impl std::fmt::Display for Vector3 {
 type IInner = glam::IVec3;
 
 #[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(C)]
 pub struct Vector3i {
     inner: IInner,
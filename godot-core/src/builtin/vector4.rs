@@ -11,6 +11,11 @@ type Inner = glam::f32::Vec4;
 //type Inner = glam::f64::DVec4;
 
 #[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(C)]
 pub struct Vector4 {
     inner: Inner,
@@ -37,6 +42,11 @@ impl std::fmt::Display for Vector4 {
 type IInner = glam::IVec4;
 
 #[derive(Default, Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(C)]
 pub struct Vector4i {
     inner: IInner,
@@ -7,13 +7,13 @@
 //! Godot engine classes and methods.
 
 // Re-exports of generated symbols
-use crate::builtin::{GodotString, NodePath};
-use crate::engine::resource_loader::CacheMode;
-use crate::obj::dom::EngineDomain;
+use crate::builtin::{Array, Callable, GodotString, NodePath, Variant};
+use crate::engine::resource_loader::{CacheMode, ThreadLoadStatus};
 use crate::obj::{Gd, GodotClass, Inherits};
 pub use gen::central_core::global;
 pub use gen::classes::*;
 pub use gen::utilities;
+use std::marker::PhantomData;
 
 /// Output of generated code.
 pub(super) mod gen {
@@ -92,22 +92,13 @@ impl NodeExt for Node {
 
 impl<U> NodeExt for Gd<U>
 where
-    U: GodotClass<Declarer = EngineDomain> + Inherits<Node>,
+    U: GodotClass + Inherits<Node>,
 {
     fn try_get_node_as<T>(&self, path: impl Into<NodePath>) -> Option<Gd<T>>
     where
         T: GodotClass + Inherits<Node>,
     {
-        // TODO this could be implemented without share(), but currently lacks the proper bounds
-        // This would need more sophisticated upcast design, e.g. T::upcast_{ref|mut}::<U>() for indirect relations
-        // to make the indirect Deref more explicit
-
-        use crate::obj::Share;
-
-        let path = path.into();
-        let node = self.share().upcast::<Node>();
-
-        <Node as NodeExt>::try_get_node_as(&*node, path)
+        <Node as NodeExt>::try_get_node_as(self.upcast_ref::<Node>(), path)
     }
 }
 
@@ -188,3 +179,148 @@ where
         )
         .and_then(|res| res.try_cast::<T>())
 }
+
+/// Starts loading a resource in the background, and returns a handle to query its progress.
+///
+/// Type-safe wrapper around [`ResourceLoader::load_threaded_request()`]. The engine loads the resource on its own threads;
+/// the returned handle is used on the calling thread to check the status and retrieve the result.
+///
+/// # Example
+/// ```no_run
+/// use godot::prelude::*;
+/// use godot::engine::load_threaded;
+///
+/// let loading = load_threaded::<PackedScene>("res://path/to/Level.tscn");
+/// // ... later, e.g. in process():
+/// if loading.is_finished() {
+///     let scene = loading.get().expect("failed to load scene");
+/// }
+/// ```
+pub fn load_threaded<T>(path: impl Into<GodotString>) -> ThreadedLoad<T>
+where
+    T: GodotClass + Inherits<Resource>,
+{
+    let path = path.into();
+    ResourceLoader::singleton().load_threaded_request(path.clone(), T::CLASS_NAME.into(), false);
+
+    ThreadedLoad {
+        path,
+        _marker: PhantomData,
+    }
+}
+
+/// Handle to a resource being loaded in the background. Obtained through [`load_threaded()`].
+#[derive(Debug)]
+pub struct ThreadedLoad<T> {
+    path: GodotString,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> ThreadedLoad<T>
+where
+    T: GodotClass + Inherits<Resource>,
+{
+    /// Current loading status.
+    pub fn status(&self) -> ThreadLoadStatus {
+        ResourceLoader::singleton().load_threaded_get_status(self.path.clone(), Array::default())
+    }
+
+    /// Whether loading has completed, either successfully or with an error.
+    pub fn is_finished(&self) -> bool {
+        self.status() != ThreadLoadStatus::THREAD_LOAD_IN_PROGRESS
+    }
+
+    /// Returns the loaded resource, blocking until loading has completed.
+    ///
+    /// Returns `None` if loading failed, or the resource does not have type `T` or inherited.
+    pub fn get(self) -> Option<Gd<T>> {
+        ResourceLoader::singleton()
+            .load_threaded_get(self.path)
+            .and_then(|res| res.try_cast::<T>())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Thread APIs
+
+/// Lock guard for a Godot [`Mutex`], unlocking it when dropped.
+///
+/// Returned by [`Mutex::lock()`] and [`Mutex::try_lock()`]. This replaces the manual `lock()`/`unlock()` pairs
+/// of the Godot API, which could be unbalanced.
+pub struct MutexGuard<'a> {
+    mutex: &'a mut Mutex,
+}
+
+impl Mutex {
+    /// Locks the mutex, blocking until it becomes available. The mutex is unlocked when the guard is dropped.
+    pub fn lock(&mut self) -> MutexGuard<'_> {
+        self.raw_lock();
+        MutexGuard { mutex: self }
+    }
+
+    /// Tries to lock the mutex without blocking. Returns `None` if it is locked by another thread.
+    pub fn try_lock(&mut self) -> Option<MutexGuard<'_>> {
+        if self.raw_try_lock() == global::Error::OK {
+            Some(MutexGuard { mutex: self })
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for MutexGuard<'_> {
+    fn drop(&mut self) {
+        self.mutex.raw_unlock();
+    }
+}
+
+/// Handle to a running Godot [`Thread`], waiting for it to finish when dropped.
+///
+/// Returned by [`Thread::spawn()`]. Godot requires `wait_to_finish()` to be called exactly once before a thread
+/// object is freed; the handle guarantees this.
+pub struct JoinHandle {
+    thread: Option<Gd<Thread>>,
+}
+
+impl Thread {
+    /// Starts a new thread running `callable`, and returns a handle to join it.
+    ///
+    /// The callable is invoked on the new thread. Objects of Rust classes can only be bound on the thread owning them,
+    /// so calling methods of Rust objects created on this thread fails the thread check (see
+    /// [`SendGd`][crate::obj::SendGd]). For Rust code, use `std::thread` together with `SendGd` instead.
+    pub fn spawn(
+        callable: Callable,
+        priority: thread::Priority,
+    ) -> Result<JoinHandle, global::Error> {
+        let mut thread = Thread::new();
+        match thread.raw_start(callable, priority) {
+            global::Error::OK => Ok(JoinHandle {
+                thread: Some(thread),
+            }),
+            error => Err(error),
+        }
+    }
+}
+
+impl JoinHandle {
+    /// Whether the thread is still running its callable.
+    pub fn is_alive(&self) -> bool {
+        self.thread
+            .as_ref()
+            .map_or(false, |thread| thread.is_alive())
+    }
+
+    /// Blocks until the thread has finished, and returns the value returned by its callable.
+    pub fn join(mut self) -> Variant {
+        let mut thread = self.thread.take().expect("thread joined only once");
+        thread.raw_wait_to_finish()
+    }
+}
+
+impl Drop for JoinHandle {
+    fn drop(&mut self) {
+        if let Some(mut thread) = self.thread.take() {
+            thread.raw_wait_to_finish();
+        }
+    }
+}
@@ -134,6 +134,11 @@ impl InitHandle {
         if let Some(layer) = self.layers.get_mut(&level) {
             layer.deinitialize()
         }
+
+        // Layers are deinitialized from highest to lowest; after the last one, everything should have been cleaned up
+        if level == self.lowest_init_level() {
+            crate::obj::leaks::report_leaks();
+        }
     }
 }
 
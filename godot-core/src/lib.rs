@@ -27,8 +27,12 @@ pub mod private {
     pub trait You_forgot_the_attribute__godot_api {}
 
     pub use crate::engine::gen::classes::class_macros;
-    pub use crate::registry::{callbacks, ClassPlugin, ErasedRegisterFn, PluginComponent};
-    pub use crate::storage::as_storage;
+    pub use crate::obj::DynTraitImpl;
+    pub use crate::registry::{
+        callbacks, generic_probe, register_integer_constant, ClassPlugin, ErasedRegisterFn,
+        PluginComponent,
+    };
+    pub use crate::storage::{as_storage, BindSite};
     pub use crate::{
         gdext_register_method, gdext_register_method_inner, gdext_virtual_method_callback,
     };
@@ -41,6 +45,20 @@ pub mod private {
         sys::plugin_foreach!(__GODOT_PLUGIN_REGISTRY; visitor);
     }
 
+    sys::plugin_registry!(__GODOT_DYN_REGISTRY: DynTraitImpl);
+
+    pub(crate) fn find_dyn_impl(predicate: impl Fn(&DynTraitImpl) -> bool) -> Option<DynTraitImpl> {
+        let mut found = None;
+        let mut visitor = |dyn_impl: &DynTraitImpl| {
+            if found.is_none() && predicate(dyn_impl) {
+                found = Some(dyn_impl.clone());
+            }
+        };
+
+        sys::plugin_foreach!(__GODOT_DYN_REGISTRY; visitor);
+        found
+    }
+
     pub fn print_panic(err: Box<dyn std::any::Any + Send>) {
         if let Some(s) = err.downcast_ref::<&'static str>() {
             log::godot_error!("rust-panic:  {}", s);
@@ -149,7 +149,7 @@ macro_rules! gdext_register_method_inner {
             let mut arguments_metadata: [sys::GDNativeExtensionClassMethodArgumentMetadata; NUM_ARGS]
                 = std::array::from_fn(|i| Sig::param_metadata(i as i32));
 
-            let class_name = StringName::from(stringify!($Class));
+            let class_name = StringName::from(<$Class as $crate::obj::GodotClass>::CLASS_NAME);
             let method_name = StringName::from(stringify!($method_name));
 
             // println!("REG {class_name}::{method_name}");
@@ -279,6 +279,7 @@ macro_rules! gdext_register_method {
 macro_rules! gdext_virtual_method_callback_inner {
     (
         $Class:ty,
+        $Trait:path,
         $map_method:ident,
         fn $method_name:ident(
             $( $arg:ident : $Param:ty, )*
@@ -287,18 +288,19 @@ macro_rules! gdext_virtual_method_callback_inner {
         Some({
             use $crate::sys;
 
-            unsafe extern "C" fn function(
+            // Generic over the class, since nested fns cannot refer to generic parameters of a generic class's impl
+            unsafe extern "C" fn function<C: $Trait>(
                 instance_ptr: sys::GDExtensionClassInstancePtr,
                 args: *const sys::GDNativeTypePtr,
                 ret: sys::GDNativeTypePtr,
             ) {
                 $crate::gdext_ptrcall!(
                     instance_ptr, args, ret;
-                    $Class;
+                    C;
                     fn $method_name( $( $arg : $Param, )* ) -> $Ret
                 );
             }
-            function
+            function::<$Class>
         })
     };
 }
@@ -312,6 +314,7 @@ macro_rules! gdext_virtual_method_callback {
     // mutable
     (
         $Class:ty,
+        $Trait:path,
         fn $method_name:ident(
             &mut self
             $(, $param:ident : $ParamTy:ty)*
@@ -320,6 +323,7 @@ macro_rules! gdext_virtual_method_callback {
     ) => {
         $crate::gdext_virtual_method_callback_inner!(
             $Class,
+            $Trait,
             map_mut,
             fn $method_name(
                 $( $param : $ParamTy, )*
@@ -330,6 +334,7 @@ macro_rules! gdext_virtual_method_callback {
     // immutable
     (
         $Class:ty,
+        $Trait:path,
         fn $method_name:ident(
             &self
             $(, $param:ident : $ParamTy:ty)*
@@ -338,6 +343,7 @@ macro_rules! gdext_virtual_method_callback {
     ) => {
         $crate::gdext_virtual_method_callback_inner!(
             $Class,
+            $Trait,
             map,
             fn $method_name(
                 $( $param : $ParamTy, )*
@@ -348,6 +354,7 @@ macro_rules! gdext_virtual_method_callback {
     // mutable without return type
     (
         $Class:ty,
+        $Trait:path,
         fn $method_name:ident(
             &mut self
             $(, $param:ident : $ParamTy:ty)*
@@ -357,6 +364,7 @@ macro_rules! gdext_virtual_method_callback {
         // recurse this macro
         $crate::gdext_virtual_method_callback!(
             $Class,
+            $Trait,
             fn $method_name(
                 &mut self
                 $(, $param : $ParamTy )*
@@ -367,6 +375,7 @@ macro_rules! gdext_virtual_method_callback {
     // immutable without return type
     (
         $Class:ty,
+        $Trait:path,
         fn $method_name:ident(
             &self
             $(, $param:ident : $ParamTy:ty)*
@@ -376,6 +385,7 @@ macro_rules! gdext_virtual_method_callback {
         // recurse this macro
         $crate::gdext_virtual_method_callback!(
             $Class,
+            $Trait,
             fn $method_name(
                 &self
                 $(, $param : $ParamTy )*
@@ -398,7 +408,7 @@ macro_rules! gdext_ptrcall {
 
         $crate::out!("ptrcall: {}", stringify!($method_name));
         let storage = $crate::private::as_storage::<$Class>($instance_ptr);
-        let mut instance = storage.get_mut();
+        let mut instance = storage.get_mut($crate::private::BindSite::Method(stringify!($method_name)));
 
         let mut idx = 0;
         $(
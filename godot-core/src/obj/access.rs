@@ -0,0 +1,163 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::panic::Location;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::obj::InstanceId;
+
+/// Whether accesses through `Gd` are validated. Always in debug builds, in release builds only with feature `safeguards`.
+pub(crate) const SAFEGUARDS: bool = cfg!(any(debug_assertions, feature = "safeguards"));
+
+/// Error when accessing an object through a `Gd` smart pointer fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccessError {
+    /// The object has been freed, or is in the process of being destroyed.
+    Freed {
+        /// Static type of the `Gd` smart pointer.
+        class_name: &'static str,
+        instance_id: Option<InstanceId>,
+        /// Where the object was freed, if known.
+        freed_at: String,
+    },
+
+    /// The user instance is already bound in an incompatible way (`&T` vs. `&mut T`).
+    AlreadyBound {
+        class_name: &'static str,
+        instance_id: Option<InstanceId>,
+        /// Which guards are outstanding, and where they were bound.
+        details: String,
+    },
+
+    /// The user instance belongs to another thread; see [`SendGd`][crate::obj::SendGd].
+    WrongThread {
+        class_name: &'static str,
+        instance_id: Option<InstanceId>,
+        /// Owning and accessing thread.
+        details: String,
+    },
+}
+
+impl AccessError {
+    pub(crate) fn freed(class_name: &'static str, instance_id: Option<InstanceId>) -> Self {
+        let freed_at = match instance_id {
+            Some(id) => describe_free(id),
+            None => "freed at unknown location".to_string(),
+        };
+
+        Self::Freed {
+            class_name,
+            instance_id,
+            freed_at,
+        }
+    }
+}
+
+impl Display for AccessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let id_str = |id: &Option<InstanceId>| match id {
+            Some(id) => id.to_string(),
+            None => "(none)".to_string(),
+        };
+
+        match self {
+            Self::Freed {
+                class_name,
+                instance_id,
+                freed_at,
+            } => write!(
+                f,
+                "access to dead object of class {class_name} (instance ID {}); object was {freed_at}",
+                id_str(instance_id)
+            ),
+            Self::AlreadyBound {
+                class_name,
+                instance_id,
+                details,
+            } => write!(
+                f,
+                "object of class {class_name} (instance ID {}) is already bound.\n{details}",
+                id_str(instance_id)
+            ),
+            Self::WrongThread {
+                class_name,
+                instance_id,
+                details,
+            } => write!(
+                f,
+                "object of class {class_name} (instance ID {}) is owned by another thread.\n{details}",
+                id_str(instance_id)
+            ),
+        }
+    }
+}
+
+impl Error for AccessError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Registry of freed objects
+
+/// How an object was destroyed, if the destruction was initiated from Rust.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum FreeSite {
+    /// Explicit `Gd::free()` call.
+    Free(&'static Location<'static>),
+
+    /// Last `Gd` reference to a ref-counted object dropped.
+    LastReference,
+}
+
+// Bounded, so that long-running debug sessions do not accumulate memory. Oldest entries are evicted first.
+const MAX_TRACKED_FREES: usize = 4096;
+
+#[derive(Default)]
+struct FreedObjects {
+    sites: HashMap<InstanceId, (String, FreeSite)>,
+    order: VecDeque<InstanceId>,
+}
+
+static FREED_OBJECTS: Lazy<Mutex<FreedObjects>> = Lazy::new(Default::default);
+
+/// Remembers where the object `instance_id` with dynamic class `class_name` was freed. No-op without safeguards.
+pub(crate) fn record_free(instance_id: InstanceId, class_name: String, site: FreeSite) {
+    if !SAFEGUARDS {
+        return;
+    }
+
+    let mut freed = FREED_OBJECTS.lock().unwrap();
+    if freed.order.len() >= MAX_TRACKED_FREES {
+        if let Some(oldest) = freed.order.pop_front() {
+            freed.sites.remove(&oldest);
+        }
+    }
+
+    if freed
+        .sites
+        .insert(instance_id, (class_name, site))
+        .is_none()
+    {
+        freed.order.push_back(instance_id);
+    }
+}
+
+fn describe_free(instance_id: InstanceId) -> String {
+    let freed = FREED_OBJECTS.lock().unwrap();
+
+    match freed.sites.get(&instance_id) {
+        Some((class, FreeSite::Free(location))) => {
+            format!("a {class}, freed by Gd::free() at {location}")
+        }
+        Some((class, FreeSite::LastReference)) => {
+            format!("a {class}, freed when its last reference was dropped")
+        }
+        None => "freed outside of Rust (e.g. by the engine or GDScript)".to_string(),
+    }
+}
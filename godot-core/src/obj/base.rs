@@ -7,10 +7,13 @@
 use crate::builtin::GodotString;
 use crate::obj::Gd;
 use crate::obj::GodotClass;
+use crate::storage::{as_storage, nop_instance_callbacks, BorrowSuspension};
 use crate::{engine, sys};
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut};
+use sys::interface_fn;
 
 /// Restricted version of `Gd`, to hold the base instance inside a user's `GodotClass`.
 ///
@@ -92,3 +95,85 @@ impl<T: GodotClass> DerefMut for Base<T> {
         &mut self.obj
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Implemented for user classes that have a `#[base]` field. Auto-implemented by `#[derive(GodotClass)]`.
+pub trait WithBaseField: GodotClass {
+    #[doc(hidden)]
+    fn __base(&self) -> &Base<Self::Base>;
+
+    /// Accesses the base object for calls into the engine, while allowing re-entrant calls into this instance.
+    ///
+    /// When the engine calls a method of your class (e.g. a `#[func]`), the user instance is bound exclusively for the
+    /// duration of that call. If the method in turn calls the engine -- for example emitting a signal, or calling GDScript --
+    /// and the engine calls back into the same object, that second call would fail because the instance is already bound.
+    ///
+    /// Performing such calls through the guard returned by `base_mut()` lifts the outer borrow for as long as the guard lives.
+    /// Since the guard borrows `&mut self`, your own fields cannot be accessed in the meantime, so re-entrant calls may bind
+    /// the instance again.
+    /// ```ignore
+    /// #[func]
+    /// fn take_damage(&mut self, amount: i64) {
+    ///     self.health -= amount;
+    ///
+    ///     // Signal handlers may call methods on this object again
+    ///     self.base_mut().emit_signal("damaged".into(), &[amount.to_variant()]);
+    /// }
+    /// ```
+    fn base_mut(&mut self) -> BaseMut<'_, Self> {
+        BaseMut::new(self)
+    }
+}
+
+/// Guard returned by [`WithBaseField::base_mut()`], dereferencing to the base object.
+pub struct BaseMut<'a, T: GodotClass> {
+    // Non-owning, like Base<T>
+    obj: ManuallyDrop<Gd<T::Base>>,
+
+    // Declared after `obj`, so the borrow is restored last
+    _suspension: Option<BorrowSuspension>,
+    _borrow: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: WithBaseField> BaseMut<'a, T> {
+    fn new(user_instance: &'a mut T) -> Self {
+        let object_ptr = user_instance.__base().obj_sys();
+        let callbacks = nop_instance_callbacks();
+
+        // Null binding: the instance is not yet stored (e.g. still in init()), so it is not bound either
+        let suspension = unsafe {
+            let binding = interface_fn!(object_get_instance_binding)(
+                object_ptr,
+                sys::get_library(),
+                &callbacks,
+            );
+
+            if binding.is_null() {
+                None
+            } else {
+                Some(as_storage::<T>(binding).suspend_borrow())
+            }
+        };
+
+        Self {
+            obj: ManuallyDrop::new(unsafe { Gd::from_obj_sys(object_ptr) }),
+            _suspension: suspension,
+            _borrow: PhantomData,
+        }
+    }
+}
+
+impl<T: GodotClass> Deref for BaseMut<'_, T> {
+    type Target = Gd<T::Base>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.obj
+    }
+}
+
+impl<T: GodotClass> DerefMut for BaseMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.obj
+    }
+}
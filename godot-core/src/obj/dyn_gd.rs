@@ -0,0 +1,129 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::engine::Object;
+use crate::obj::{dom, Gd, GodotClass};
+use crate::storage::{BindSite, BorrowGuard};
+
+/// Exclusive access to a user instance through a trait object `D`, e.g. `dyn Damageable`.
+///
+/// Obtained through [`Gd::try_dyn()`]. Like [`GdMut`][crate::obj::GdMut], this holds an exclusive borrow of the
+/// user instance until dropped.
+pub struct DynGdMut<'a, D: ?Sized> {
+    // Points into the user instance, which is kept alive by `_obj` and borrowed by `_guard`
+    value: *mut D,
+    _guard: BorrowGuard,
+    _obj: Gd<Object>,
+    _marker: PhantomData<&'a mut D>,
+}
+
+impl<D: ?Sized> Deref for DynGdMut<'_, D> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        // SAFETY: exclusive borrow is held by the guard
+        unsafe { &*self.value }
+    }
+}
+
+impl<D: ?Sized> DerefMut for DynGdMut<'_, D> {
+    fn deref_mut(&mut self) -> &mut D {
+        // SAFETY: exclusive borrow is held by the guard
+        unsafe { &mut *self.value }
+    }
+}
+
+impl<D: ?Sized> Debug for DynGdMut<'_, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DynGdMut").field("obj", &self._obj).finish()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Registry
+
+/// Converts an object into a trait object `D`; `None` if the object is not of the implementing class.
+type DynCaster<D> =
+    Box<dyn Fn(&Gd<Object>, BindSite) -> Option<DynGdMut<'static, D>> + Send + Sync>;
+
+/// Trait impl declared with `#[godot_dyn]`, collected through the plugin system.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct DynTraitImpl {
+    class_name: &'static str,
+    dyn_trait_type: TypeId,
+    dyn_trait_name: &'static str,
+
+    // Holds a DynCaster<D>, with D being the trait object type. Shared, so it can be invoked outside the registry lock.
+    caster: Arc<dyn Any + Send + Sync>,
+}
+
+impl DynTraitImpl {
+    /// Registers `impl D for T`. `cast` performs the unsizing coercion from `&mut T` to `&mut D`.
+    pub fn new<T, D>(cast: fn(&mut T) -> &mut D) -> Self
+    where
+        T: GodotClass<Declarer = dom::UserDomain>,
+        D: ?Sized + 'static,
+    {
+        let caster: DynCaster<D> = Box::new(move |obj: &Gd<Object>, site: BindSite| {
+            let typed = obj.share().owned_cast::<T>().ok()?;
+
+            // Panics on conflicting borrows, like bind_mut()
+            let (value, guard) = typed.storage().get_mut(site).into_parts();
+            let value: *mut D = cast(value);
+
+            Some(DynGdMut {
+                value,
+                _guard: guard,
+                _obj: obj.share(),
+                _marker: PhantomData,
+            })
+        });
+
+        Self {
+            class_name: T::CLASS_NAME,
+            dyn_trait_type: TypeId::of::<D>(),
+            dyn_trait_name: std::any::type_name::<D>(),
+            caster: Arc::new(caster),
+        }
+    }
+
+    /// Whether this is the impl of trait object `D` for the class `class_name`.
+    pub(crate) fn matches<D: ?Sized + 'static>(&self, class_name: &str) -> bool {
+        self.class_name == class_name && self.dyn_trait_type == TypeId::of::<D>()
+    }
+
+    /// Binds `obj` as trait object `D`. Must only be called if [`Self::matches()`] holds for `obj`'s dynamic class.
+    pub(crate) fn cast<'a, D>(&self, obj: &Gd<Object>, site: BindSite) -> Option<DynGdMut<'a, D>>
+    where
+        D: ?Sized + 'static,
+    {
+        let caster = self
+            .caster
+            .downcast_ref::<DynCaster<D>>()
+            .expect("caster has the type of the registered trait object");
+
+        // Reborrow with the caller's lifetime; the 'static is only needed to store the caster
+        caster(obj, site).map(|guard| DynGdMut {
+            value: guard.value,
+            _guard: guard._guard,
+            _obj: guard._obj,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl Debug for DynTraitImpl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "impl {} for {}", self.dyn_trait_name, self.class_name)
+    }
+}
@@ -4,9 +4,12 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::panic::Location;
 use std::ptr;
 
 use godot_ffi as sys;
@@ -17,11 +20,12 @@ use sys::{ffi_methods, interface_fn, static_assert_eq_size, GodotFfi};
 use crate::builtin::meta::{ClassName, PropertyInfo, VariantMetadata};
 use crate::builtin::GodotString;
 use crate::builtin::{FromVariant, StringName, ToVariant, Variant, VariantConversionError};
+use crate::obj::access::{self, AccessError, FreeSite, SAFEGUARDS};
 use crate::obj::dom::Domain as _;
 use crate::obj::mem::Memory as _;
 use crate::obj::{cap, dom, mem, GodotClass, Inherits, Share};
-use crate::obj::{GdMut, GdRef, InstanceId};
-use crate::storage::InstanceStorage;
+use crate::obj::{leaks, DynGdMut, GdMut, GdRef, InstanceId, Owned, WeakGd};
+use crate::storage::{BindConflict, BindSite, InstanceStorage};
 use crate::{callbacks, engine, out};
 
 /// Smart pointer to objects owned by the Godot engine.
@@ -44,6 +48,9 @@ use crate::{callbacks, engine, out};
 ///   reference-counted or manually-managed types at runtime. The behavior corresponds to one of the two previous points.
 ///   Note that if the dynamic type is also `Object`, the memory is manually-managed.
 ///
+/// `Gd<T>` is neither `Send` nor `Sync`, since objects belong to the thread they were created on.
+/// To move an object to another thread, use [`SendGd`][crate::obj::SendGd], which also documents the thread safety model.
+///
 /// [`Object`]: crate::engine::Object
 /// [`RefCounted`]: crate::engine::RefCounted
 pub struct Gd<T: GodotClass> {
@@ -53,6 +60,10 @@ pub struct Gd<T: GodotClass> {
     // The former is the standard FFI type, while the latter is used in obj-specific GDExtension engines.
     // pub(crate) because accessed in obj::dom
     pub(crate) opaque: OpaqueObject,
+
+    // Instance ID at the time this pointer was obtained. Allows liveness checks without dereferencing a possibly dangling `Object*`.
+    // `None` only for null pointers.
+    cached_id: Option<InstanceId>,
     _marker: PhantomData<*const T>,
 }
 
@@ -71,8 +82,8 @@ where
 {
     /// Moves a user-created obj into this smart pointer, submitting ownership to the Godot engine.
     ///
-    /// This is only useful for types `T` which do not store their base objects (if they have a base,
-    /// you cannot construct them standalone).
+    /// This is only useful for types `T` which do not store their base objects. Classes with a `#[base]` field
+    /// can be constructed through [`Gd::with_base()`].
     pub fn new(user_object: T) -> Self {
         /*let result = unsafe {
             //let ptr = interface_fn!(classdb_construct_object)(class_name.c_str());
@@ -89,6 +100,32 @@ where
         result
     }
 
+    /// Creates an instance of `T` inside a smart pointer, from a closure receiving the base object.
+    ///
+    /// Unlike [`Gd::new()`], this allows classes with a `#[base]` field to be constructed with custom arguments:
+    /// ```no_run
+    /// # use godot::prelude::*;
+    /// #[derive(GodotClass)]
+    /// #[class(base=Node)]
+    /// struct Enemy {
+    ///     #[base]
+    ///     base: Base<Node>,
+    ///     hp: i64,
+    /// }
+    ///
+    /// let enemy = Gd::with_base(|base| Enemy { base, hp: 100 });
+    /// ```
+    pub fn with_base<F>(init: F) -> Self
+    where
+        F: FnOnce(crate::obj::Base<T::Base>) -> T,
+    {
+        let object_ptr = callbacks::create_custom(init);
+        let result = unsafe { Gd::from_obj_sys(object_ptr) };
+
+        T::Mem::maybe_init_ref(&result);
+        result
+    }
+
     /// Creates a default-constructed instance of `T` inside a smart pointer.
     ///
     /// This is equivalent to the GDScript expression `T.new()`.
@@ -126,9 +163,25 @@ where
     /// * If another `Gd` smart pointer pointing to the same Rust instance has a live `GdMut` guard bound.
     /// * If there is an ongoing function call from GDScript to Rust, which currently holds a `&mut T`
     ///   reference to the user instance. This can happen through re-entrancy (Rust -> GDScript -> Rust call).
+    /// * If the obj has been freed (checked in debug builds, or with feature `safeguards`).
     // Note: possible names: write/read, hold/hold_mut, r/w, r/rw, ...
+    #[track_caller]
     pub fn bind(&self) -> GdRef<T> {
-        GdRef::from_cell(self.storage().get())
+        self.validate();
+        self.storage().get(BindSite::Rust(Location::caller()))
+    }
+
+    /// Like [`Self::bind()`], but returns an error instead of panicking if the obj is dead, already bound,
+    /// or owned by another thread.
+    ///
+    /// The liveness check is always performed, independently of build configuration.
+    #[track_caller]
+    pub fn try_bind(&self) -> Result<GdRef<T>, AccessError> {
+        let site = BindSite::Rust(Location::caller());
+
+        self.checked_storage()?
+            .try_get(site)
+            .map_err(|conflict| self.bind_error(conflict))
     }
 
     /// Hands out a guard for an exclusive borrow, through which the user instance can be read and written.
@@ -141,9 +194,56 @@ where
     /// # Panics
     /// * If another `Gd` smart pointer pointing to the same Rust instance has a live `GdRef` or `GdMut` guard bound.
     /// * If there is an ongoing function call from GDScript to Rust, which currently holds a `&T` or `&mut T`
-    ///   reference to the user instance. This can happen through re-entrancy (Rust -> GDScript -> Rust call).
+    ///   reference to the user instance. This can happen through re-entrancy (Rust -> GDScript -> Rust call);
+    ///   see [`WithBaseField::base_mut()`][crate::obj::WithBaseField::base_mut] for how to allow it.
+    /// * If the obj has been freed (checked in debug builds, or with feature `safeguards`).
+    ///
+    /// The panic message names the outstanding guards and where they were bound.
+    #[track_caller]
     pub fn bind_mut(&mut self) -> GdMut<T> {
-        GdMut::from_cell(self.storage().get_mut())
+        self.validate();
+        self.storage().get_mut(BindSite::Rust(Location::caller()))
+    }
+
+    /// Like [`Self::bind_mut()`], but returns an error instead of panicking if the obj is dead, already bound,
+    /// or owned by another thread.
+    ///
+    /// The liveness check is always performed, independently of build configuration.
+    #[track_caller]
+    pub fn try_bind_mut(&mut self) -> Result<GdMut<T>, AccessError> {
+        let site = BindSite::Rust(Location::caller());
+
+        self.checked_storage()?
+            .try_get_mut(site)
+            .map_err(|conflict| self.bind_error(conflict))
+    }
+
+    /// Storage, if the obj is alive and not currently being destroyed.
+    fn checked_storage(&self) -> Result<&InstanceStorage<T>, AccessError> {
+        self.check_alive()?;
+
+        let storage = self.storage();
+        if storage.destroyed_by_godot() {
+            Err(AccessError::freed(T::CLASS_NAME, self.cached_id))
+        } else {
+            Ok(storage)
+        }
+    }
+
+    fn bind_error(&self, conflict: BindConflict) -> AccessError {
+        let details = conflict.to_string();
+        match conflict {
+            BindConflict::AlreadyBound { .. } => AccessError::AlreadyBound {
+                class_name: T::CLASS_NAME,
+                instance_id: self.cached_id,
+                details,
+            },
+            BindConflict::WrongThread { .. } => AccessError::WrongThread {
+                class_name: T::CLASS_NAME,
+                instance_id: self.cached_id,
+                details,
+            },
+        }
     }
 
     /// Storage obj associated with the extension instance
@@ -201,8 +301,26 @@ impl<T: GodotClass> Gd<T> {
     }
 
     fn from_opaque(opaque: OpaqueObject) -> Self {
+        let mut result = Self::from_opaque_with_id(opaque, None);
+
+        // Only place where the engine is queried for the ID; the pointer is freshly obtained from Godot and thus valid
+        let object_ptr = result.obj_sys();
+        if !object_ptr.is_null() {
+            let id = unsafe { interface_fn!(object_get_instance_id)(object_ptr) };
+            result.cached_id = InstanceId::try_from_u64(id);
+
+            if let Some(id) = result.cached_id {
+                leaks::on_engine_object::<T>(id);
+            }
+        }
+
+        result
+    }
+
+    fn from_opaque_with_id(opaque: OpaqueObject, cached_id: Option<InstanceId>) -> Self {
         Self {
             opaque,
+            cached_id,
             _marker: PhantomData,
         }
     }
@@ -211,8 +329,15 @@ impl<T: GodotClass> Gd<T> {
     ///
     pub fn instance_id_or_none(&self) -> Option<InstanceId> {
         // Note: bit 'id & (1 << 63)' determines if the instance is ref-counted
-        let id = unsafe { interface_fn!(object_get_instance_id)(self.obj_sys()) };
-        InstanceId::try_from_u64(id)
+        let id = self.cached_id?;
+
+        // Look up the ID instead of dereferencing our own pointer, which may be dangling. IDs are never reused in Godot.
+        let live_ptr = unsafe { interface_fn!(object_get_instance_from_id)(id.to_u64()) };
+        if live_ptr == self.obj_sys() {
+            Some(id)
+        } else {
+            None
+        }
     }
 
     /// Returns the instance ID of this obj (panics when dead).
@@ -238,14 +363,114 @@ impl<T: GodotClass> Gd<T> {
     /// and will panic in a defined manner. Encountering such panics is almost always a bug you should fix, and not a
     /// runtime condition to check against.
     pub fn is_instance_valid(&self) -> bool {
-        // TODO Is this really necessary, or is Godot's instance_id() guaranteed to return 0 for destroyed objects?
-        if let Some(id) = self.instance_id_or_none() {
-            engine::utilities::is_instance_id_valid(id.to_i64())
-        } else {
-            false
+        self.instance_id_or_none().is_some()
+    }
+
+    /// Whether the dynamic type of the obj inherits `RefCounted`, based on the cached instance ID.
+    ///
+    /// Does not involve the engine, so it can be used for dead objects, too. Returns `false` for null objects.
+    pub(crate) fn is_dynamic_ref_counted(&self) -> bool {
+        self.cached_id.map_or(false, |id| id.is_ref_counted())
+    }
+
+    /// Returns an error if the obj is dead.
+    pub(crate) fn check_alive(&self) -> Result<(), AccessError> {
+        match self.instance_id_or_none() {
+            Some(_) => Ok(()),
+            None => Err(AccessError::freed(T::CLASS_NAME, self.cached_id)),
+        }
+    }
+
+    /// Panics if the obj is dead. Only checked in debug builds, or with feature `safeguards`.
+    #[track_caller]
+    pub(crate) fn validate(&self) {
+        if SAFEGUARDS {
+            if let Err(err) = self.check_alive() {
+                panic!("{err}");
+            }
+        }
+    }
+
+    /// Records the dynamic class and the site where this obj is about to be destroyed (only with safeguards).
+    fn record_free(&self, site: FreeSite) {
+        if let (true, Some(id)) = (SAFEGUARDS, self.cached_id) {
+            let class: GodotString = self.as_object(|obj| engine::Object::get_class(obj));
+            access::record_free(id, class.to_string(), site);
+        }
+    }
+
+    /// Returns `true` if both smart pointers point to the same obj, independently of their static types.
+    ///
+    /// Unlike `==`, this does not involve the instance ID, but compares the raw `Object*` pointers.
+    pub fn ptr_eq<U: GodotClass>(&self, other: &Gd<U>) -> bool {
+        self.obj_sys() == other.obj_sys()
+    }
+
+    /// Identity used for comparison and hashing: the cached instance ID, or the pointer address if there is none.
+    ///
+    /// Does not depend on liveness, so a `Gd` keeps its hash and order when the obj is freed while stored in a collection.
+    fn identity(&self) -> (Option<InstanceId>, usize) {
+        match self.cached_id {
+            Some(id) => (Some(id), 0),
+            None => (None, self.obj_sys() as usize),
         }
     }
 
+    /// Creates a weak reference to this obj, which does not keep it alive.
+    ///
+    /// Use [`WeakGd::upgrade()`] to get back a `Gd<T>`, as long as the obj is still alive.
+    ///
+    /// # Panics
+    /// If this obj is no longer alive.
+    pub fn downgrade(&self) -> WeakGd<T> {
+        let instance_id = self
+            .instance_id_or_none()
+            .expect("failed to call downgrade() on destroyed obj");
+
+        WeakGd::from_instance_id(instance_id)
+    }
+
+    /// **Trait object access:** binds the user instance as trait object `D`, for example `dyn Damageable`.
+    ///
+    /// This works independently of the static type `T` (typically `Node` or `Object`). It succeeds if the dynamic class of
+    /// the obj is a user class, for which the trait has been implemented with `#[godot_dyn]`:
+    /// ```ignore
+    /// #[godot_dyn]
+    /// impl Damageable for Enemy { ... }
+    ///
+    /// let mut node: Gd<Node> = ...;
+    /// if let Some(mut damageable) = node.try_dyn::<dyn Damageable>() {
+    ///     damageable.take_damage(10);
+    /// }
+    /// ```
+    ///
+    /// Returns `None` if there is no such impl for the obj's class. The returned guard holds an exclusive borrow,
+    /// like [`bind_mut()`][Self::bind_mut].
+    ///
+    /// # Panics
+    /// * If the user instance is already bound (see `bind_mut()`).
+    /// * If the obj has been freed (checked in debug builds, or with feature `safeguards`).
+    #[track_caller]
+    pub fn try_dyn<D>(&mut self) -> Option<DynGdMut<D>>
+    where
+        D: ?Sized + 'static,
+    {
+        self.validate();
+        let site = BindSite::Rust(Location::caller());
+
+        let class_name = self
+            .as_object(|obj| engine::Object::get_class(obj))
+            .to_string();
+        let dyn_impl =
+            crate::private::find_dyn_impl(|dyn_impl| dyn_impl.matches::<D>(&class_name))?;
+
+        let obj = self
+            .share()
+            .owned_cast::<engine::Object>()
+            .expect("every class inherits Object");
+        dyn_impl.cast::<D>(&obj, site)
+    }
+
     /// Needed to initialize ref count -- must be explicitly invoked.
     ///
     /// Could be made part of FFI methods, but there are some edge cases where this is not intended.
@@ -271,6 +496,46 @@ impl<T: GodotClass> Gd<T> {
             .expect("Upcast failed. This is a bug; please report it.")
     }
 
+    /// **Upcast shared-ref:** access this obj as a shared reference to a base class.
+    ///
+    /// Unlike [`Self::upcast()`], this does not consume or share the smart pointer, and thus never touches the reference
+    /// count. This works for any direct or indirect base class, and also for user-defined `T` (e.g. `&Node` from `Gd<MyNode>`).
+    /// ```ignore
+    /// fn print_name(node: &Node) { ... }
+    ///
+    /// let obj: Gd<MyNode3D> = ...;
+    /// print_name(obj.upcast_ref::<Node>());
+    /// ```
+    pub fn upcast_ref<Base>(&self) -> &Base
+    where
+        Base: GodotClass<Declarer = dom::EngineDomain>,
+        T: Inherits<Base>,
+    {
+        // SAFETY: engine classes have the same layout as `OpaqueObject` (see Deref impl). Like owned_cast(), this relies
+        // on C++ upcasts not modifying the `Object*` pointer.
+        debug_assert_eq!(
+            std::mem::size_of::<Base>(),
+            std::mem::size_of::<OpaqueObject>()
+        );
+        unsafe { std::mem::transmute::<&OpaqueObject, &Base>(&self.opaque) }
+    }
+
+    /// **Upcast exclusive-ref:** access this obj as an exclusive reference to a base class.
+    ///
+    /// Same as [`Self::upcast_ref()`], but allows calling `&mut self` methods of the base class.
+    pub fn upcast_mut<Base>(&mut self) -> &mut Base
+    where
+        Base: GodotClass<Declarer = dom::EngineDomain>,
+        T: Inherits<Base>,
+    {
+        // SAFETY: see upcast_ref() and DerefMut impl
+        debug_assert_eq!(
+            std::mem::size_of::<Base>(),
+            std::mem::size_of::<OpaqueObject>()
+        );
+        unsafe { std::mem::transmute::<&mut OpaqueObject, &mut Base>(&mut self.opaque) }
+    }
+
     /// **Downcast:** try to convert into a smart pointer to a derived class.
     ///
     /// If `T`'s dynamic type is not `Derived` or one of its subclasses, `None` is returned
@@ -303,7 +568,7 @@ impl<T: GodotClass> Gd<T> {
     }
 
     /// Returns `Ok(cast_obj)` on success, `Err(self)` on error
-    fn owned_cast<U>(self) -> Result<Gd<U>, Self>
+    pub(crate) fn owned_cast<U>(self) -> Result<Gd<U>, Self>
     where
         U: GodotClass,
     {
@@ -313,6 +578,10 @@ impl<T: GodotClass> Gd<T> {
         //
         // The Deref/DerefMut impls for T implement an "implicit upcast" on the obj (not Gd) level and
         // rely on this (e.g. &Node3D -> &Node).
+        //
+        // The reference held by `self` (if ref-counted) is moved to the result, even if T::Mem and U::Mem differ.
+        // This is sound, because all memory strategies agree with the dynamic type: a successful cast to a
+        // StaticRefCount class implies a RefCounted obj, and DynamicRefCount checks the instance ID at runtime.
 
         let result = unsafe { self.ffi_cast::<U>() };
         match result {
@@ -338,7 +607,10 @@ impl<T: GodotClass> Gd<T> {
         if cast_object_ptr.is_null() {
             None
         } else {
-            Some(Gd::from_obj_sys(cast_object_ptr))
+            // Same obj, so the instance ID can be reused instead of querying it
+            let opaque =
+                std::mem::transmute::<sys::GDNativeObjectPtr, OpaqueObject>(cast_object_ptr);
+            Some(Gd::from_opaque_with_id(opaque, self.cached_id))
         }
     }
 
@@ -358,7 +630,7 @@ impl<T: GodotClass> Gd<T> {
     }
 
     pub(crate) fn as_object<R>(&self, apply: impl Fn(&mut engine::Object) -> R) -> R {
-        // Note: no validity check; this could be called by to_string(), which can be called on dead instances
+        // Note: validity is only checked by DerefMut with safeguards; this could be called by to_string() on dead instances
 
         let tmp = unsafe { self.ffi_cast::<engine::Object>() };
         let mut tmp = tmp.expect("obj expected to inherit Object; should never fail");
@@ -370,6 +642,17 @@ impl<T: GodotClass> Gd<T> {
         return_val
     }
 
+    /// Applies `apply` if the obj is a `Node`, otherwise returns `None`.
+    pub(crate) fn as_node<R>(&self, apply: impl Fn(&mut engine::Node) -> R) -> Option<R> {
+        let tmp = unsafe { self.ffi_cast::<engine::Node>() };
+        let mut tmp = tmp?;
+        let return_val =
+            <engine::Node as GodotClass>::Declarer::scoped_mut(&mut tmp, |obj| apply(obj));
+
+        std::mem::forget(tmp); // no ownership transfer
+        Some(return_val)
+    }
+
     // Conversions from/to Godot C++ `Object*` pointers
     ffi_methods! {
         type sys::GDNativeObjectPtr = Opaque;
@@ -401,6 +684,7 @@ where
     /// # Panics
     /// * When the referred-to obj has already been destroyed.
     /// * When this is invoked on an upcast `Gd<Object>` that dynamically points to a reference-counted type (i.e. operation not supported).
+    #[track_caller]
     pub fn free(self) {
         // TODO disallow for singletons, either only at runtime or both at compile time (new memory policy) and runtime
 
@@ -417,6 +701,8 @@ where
             "called free() on already destroyed obj"
         );
 
+        self.record_free(FreeSite::Free(Location::caller()));
+
         // This destroys the Storage instance, no need to run destructor again
         unsafe {
             interface_fn!(object_destroy)(self.obj_sys());
@@ -424,36 +710,68 @@ where
 
         std::mem::forget(self);
     }
+
+    /// Takes ownership of the manually-managed obj, freeing it once the returned guard is dropped.
+    ///
+    /// See [`Owned`] for the exact semantics. Ownership can be given up again with [`Owned::release()`].
+    ///
+    /// # Panics
+    /// * When the referred-to obj has already been destroyed.
+    /// * When this is invoked on an upcast `Gd<Object>` that dynamically points to a reference-counted type.
+    pub fn into_owned(self) -> Owned<T> {
+        let ref_counted = T::Mem::is_ref_counted(&self);
+        assert_ne!(
+            ref_counted,
+            Some(true),
+            "called into_owned() on Gd<Object> which points to a RefCounted dynamic type; only supported for manually managed types."
+        );
+        assert!(
+            ref_counted == Some(false) && self.is_instance_valid(),
+            "called into_owned() on already destroyed obj"
+        );
+
+        Owned::new(self)
+    }
 }
 
-impl<T> Deref for Gd<T>
+/// _The methods in this impl block are only available for engine classes `T`, like `Node` or `RefCounted`._
+impl<T> Gd<T>
 where
     T: GodotClass<Declarer = dom::EngineDomain>,
 {
-    type Target = T;
+    /// Like `Deref`, but returns an error instead of panicking if the obj is dead.
+    ///
+    /// The liveness check is always performed, independently of build configuration.
+    pub fn try_deref(&self) -> Result<&T, AccessError> {
+        self.check_alive()?;
+        Ok(self.deref_unchecked())
+    }
 
-    fn deref(&self) -> &Self::Target {
+    /// Like `DerefMut`, but returns an error instead of panicking if the obj is dead.
+    ///
+    /// The liveness check is always performed, independently of build configuration.
+    pub fn try_deref_mut(&mut self) -> Result<&mut T, AccessError> {
+        self.check_alive()?;
+        Ok(self.deref_mut_unchecked())
+    }
+
+    fn deref_unchecked(&self) -> &T {
         // SAFETY:
-        // This relies on Gd<Node3D> having the layout as Node3D (as an example),
+        // This relies on the `opaque` field of Gd<Node3D> having the layout as Node3D (as an example),
         // which also needs #[repr(transparent)]:
         //
         // struct Gd<T: GodotClass> {
         //     opaque: OpaqueObject,         <- size of GDNativeObjectPtr
-        //     _marker: PhantomData,         <- ZST
+        //     ...
         // }
         // struct Node3D {
         //     object_ptr: sys::GDNativeObjectPtr,
         // }
         unsafe { std::mem::transmute::<&OpaqueObject, &T>(&self.opaque) }
     }
-}
 
-impl<T> DerefMut for Gd<T>
-where
-    T: GodotClass<Declarer = dom::EngineDomain>,
-{
-    fn deref_mut(&mut self) -> &mut T {
-        // SAFETY: see also Deref
+    fn deref_mut_unchecked(&mut self) -> &mut T {
+        // SAFETY: see also deref_unchecked()
         //
         // The resulting &mut T is transmuted from &mut OpaqueObject, i.e. a *pointer* to the `opaque` field.
         // `opaque` itself has a different *address* for each Gd instance, meaning that two simultaneous
@@ -463,6 +781,34 @@ where
     }
 }
 
+/// Dereferences to the engine class, to call its methods.
+///
+/// # Panics
+/// If the obj has been freed (checked in debug builds, or with feature `safeguards`).
+impl<T> Deref for Gd<T>
+where
+    T: GodotClass<Declarer = dom::EngineDomain>,
+{
+    type Target = T;
+
+    #[track_caller]
+    fn deref(&self) -> &T {
+        self.validate();
+        self.deref_unchecked()
+    }
+}
+
+impl<T> DerefMut for Gd<T>
+where
+    T: GodotClass<Declarer = dom::EngineDomain>,
+{
+    #[track_caller]
+    fn deref_mut(&mut self) -> &mut T {
+        self.validate();
+        self.deref_mut_unchecked()
+    }
+}
+
 impl<T: GodotClass> GodotFfi for Gd<T> {
     ffi_methods! { type sys::GDNativeTypePtr = Opaque; .. }
 }
@@ -502,6 +848,7 @@ impl<T: GodotClass> Drop for Gd<T> {
         out!("Gd::drop   <{}>", std::any::type_name::<T>());
         let is_last = T::Mem::maybe_dec_ref(&self); // may drop
         if is_last {
+            self.record_free(FreeSite::LastReference);
             unsafe {
                 interface_fn!(object_destroy)(self.obj_sys());
             }
@@ -527,7 +874,7 @@ impl<T: GodotClass> Drop for Gd<T> {
 impl<T: GodotClass> Share for Gd<T> {
     fn share(&self) -> Self {
         out!("Gd::share");
-        Self::from_opaque(self.opaque).ready()
+        Self::from_opaque_with_id(self.opaque, self.cached_id).ready()
     }
 }
 
@@ -566,6 +913,37 @@ impl<T: GodotClass> ToVariant for Gd<T> {
     }
 }
 
+/// Identity comparison: two `Gd` pointers are equal if they refer to the same obj (same instance ID).
+///
+/// This also works across different static types, e.g. `Gd<Node2D>` and `Gd<Node>` pointing to the same node.
+/// User-defined `PartialEq` impls on `T` are not considered.
+impl<T: GodotClass, U: GodotClass> PartialEq<Gd<U>> for Gd<T> {
+    fn eq(&self, other: &Gd<U>) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl<T: GodotClass> Eq for Gd<T> {}
+
+/// Orders by instance ID. The order is stable, but carries no further meaning.
+impl<T: GodotClass, U: GodotClass> PartialOrd<Gd<U>> for Gd<T> {
+    fn partial_cmp(&self, other: &Gd<U>) -> Option<Ordering> {
+        Some(self.identity().cmp(&other.identity()))
+    }
+}
+
+impl<T: GodotClass> Ord for Gd<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.identity().cmp(&other.identity())
+    }
+}
+
+impl<T: GodotClass> Hash for Gd<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state)
+    }
+}
+
 impl<T> Display for Gd<T>
 where
     T: GodotClass<Declarer = dom::EngineDomain>,
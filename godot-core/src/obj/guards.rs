@@ -4,21 +4,25 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::cell;
-use std::fmt::Debug;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
 use std::ops::{Deref, DerefMut};
 
+use crate::storage::BorrowGuard;
+
 /// Immutably/shared bound reference guard for a [`Gd`][crate::obj::Gd] smart pointer.
 ///
 /// See [`Gd::bind`][crate::obj::Gd::bind] for usage.
-#[derive(Debug)]
 pub struct GdRef<'a, T> {
-    cell_ref: cell::Ref<'a, T>,
+    value: &'a T,
+    _guard: BorrowGuard,
 }
 
 impl<'a, T> GdRef<'a, T> {
-    pub(crate) fn from_cell(cell_ref: cell::Ref<'a, T>) -> Self {
-        Self { cell_ref }
+    pub(crate) fn new(value: &'a T, guard: BorrowGuard) -> Self {
+        Self {
+            value,
+            _guard: guard,
+        }
     }
 }
 
@@ -26,7 +30,13 @@ impl<T> Deref for GdRef<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &T {
-        self.cell_ref.deref()
+        self.value
+    }
+}
+
+impl<T: Debug> Debug for GdRef<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("GdRef").field("value", &self.value).finish()
     }
 }
 
@@ -37,14 +47,22 @@ impl<T> Deref for GdRef<'_, T> {
 /// Mutably/exclusively bound reference guard for a [`Gd`][crate::obj::Gd] smart pointer.
 ///
 /// See [`Gd::bind_mut`][crate::obj::Gd::bind_mut] for usage.
-#[derive(Debug)]
 pub struct GdMut<'a, T> {
-    cell_ref: cell::RefMut<'a, T>,
+    value: &'a mut T,
+    _guard: BorrowGuard,
 }
 
 impl<'a, T> GdMut<'a, T> {
-    pub(crate) fn from_cell(cell_ref: cell::RefMut<'a, T>) -> Self {
-        Self { cell_ref }
+    pub(crate) fn new(value: &'a mut T, guard: BorrowGuard) -> Self {
+        Self {
+            value,
+            _guard: guard,
+        }
+    }
+
+    /// Splits the guard, so that the reference can be converted (e.g. to a trait object) while keeping the borrow.
+    pub(crate) fn into_parts(self) -> (&'a mut T, BorrowGuard) {
+        (self.value, self._guard)
     }
 }
 
@@ -52,12 +70,18 @@ impl<T> Deref for GdMut<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &T {
-        self.cell_ref.deref()
+        self.value
     }
 }
 
 impl<T> DerefMut for GdMut<'_, T> {
     fn deref_mut(&mut self) -> &mut T {
-        self.cell_ref.deref_mut()
+        self.value
+    }
+}
+
+impl<T: Debug> Debug for GdMut<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("GdMut").field("value", &self.value).finish()
     }
 }
@@ -127,3 +127,20 @@ impl VariantMetadata for InstanceId {
         sys::GDNATIVE_EXTENSION_METHOD_ARGUMENT_METADATA_INT_IS_INT64
     }
 }
+
+// Serialized as i64, the canonical representation in GDScript (see above)
+#[cfg(feature = "serde")]
+impl serde::Serialize for InstanceId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.to_i64())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for InstanceId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = i64::deserialize(deserializer)?;
+        InstanceId::try_from_i64(id)
+            .ok_or_else(|| serde::de::Error::custom("expected non-zero instance ID"))
+    }
+}
@@ -0,0 +1,314 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Tracking of live objects, to find leaks.
+//!
+//! With the `leak-tracker` feature, every instance of a user-defined class is recorded from construction until
+//! destruction. Optionally, engine objects are recorded as soon as Rust obtains a `Gd` to them, see
+//! [`set_track_engine_objects()`]. When the extension is unloaded, all objects still alive are reported as leaks.
+//!
+//! Without the feature, nothing is recorded and all snapshots are empty.
+//!
+//! In tests, [`snapshot()`] and [`Snapshot::diff()`] can be used to check that a piece of code cleans up after itself:
+//! ```no_run
+//! use godot::obj::leaks;
+//! use godot::prelude::*;
+//!
+//! let before = leaks::snapshot();
+//! let node = Node::new_alloc();
+//! node.free();
+//!
+//! let diff = before.diff(&leaks::snapshot());
+//! assert!(diff.created.is_empty(), "leaked objects:\n{diff}");
+//! ```
+
+use crate::obj::{dom, GodotClass, InstanceId};
+use godot_ffi as sys;
+use once_cell::sync::Lazy;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use sys::interface_fn;
+
+/// Whether the crate was compiled with the `leak-tracker` feature.
+pub const ENABLED: bool = cfg!(feature = "leak-tracker");
+
+static TRACK_ENGINE_OBJECTS: AtomicBool = AtomicBool::new(false);
+static CAPTURE_BACKTRACES: AtomicBool = AtomicBool::new(false);
+static NEXT_STORAGE_TOKEN: AtomicU64 = AtomicU64::new(1);
+
+static LIVE_OBJECTS: Lazy<Mutex<BTreeMap<TrackKey, LiveObject>>> = Lazy::new(Default::default);
+
+/// Also record engine objects (like `Node` or `Resource`) when a `Gd` pointing to them is obtained. Off by default.
+///
+/// Engine objects are only seen when they pass through Rust, so objects created and kept exclusively in GDScript or
+/// the engine are never reported.
+pub fn set_track_engine_objects(enabled: bool) {
+    TRACK_ENGINE_OBJECTS.store(enabled, Ordering::Relaxed);
+}
+
+/// Capture a backtrace whenever a tracked object is recorded. Off by default, as this is slow.
+pub fn set_capture_backtraces(enabled: bool) {
+    CAPTURE_BACKTRACES.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns all tracked objects which are currently alive.
+pub fn snapshot() -> Snapshot {
+    if !ENABLED {
+        return Snapshot::default();
+    }
+
+    let mut live = LIVE_OBJECTS.lock().unwrap();
+
+    // Engine objects are not notified about their destruction, so prune the ones which no longer exist
+    live.retain(|key, _| match key {
+        TrackKey::Storage(_) => true,
+        TrackKey::Engine(id) => is_alive(*id),
+    });
+
+    let user_ids: Vec<InstanceId> = live
+        .values()
+        .filter(|obj| obj.kind == ObjectKind::User)
+        .filter_map(|obj| obj.instance_id)
+        .collect();
+
+    // An object may have been recorded both as user instance and via a Gd to its engine base class
+    let objects = live
+        .iter()
+        .filter(|(key, _)| match key {
+            TrackKey::Storage(_) => true,
+            TrackKey::Engine(id) => !user_ids.contains(id),
+        })
+        .map(|(key, obj)| (*key, obj.clone()))
+        .collect();
+
+    Snapshot { objects }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// Kind of a tracked object.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ObjectKind {
+    /// Instance of a user-defined class, tracked from construction to destruction.
+    User,
+
+    /// Engine object, tracked from the first time a `Gd` to it was obtained.
+    Engine,
+}
+
+/// An object which was alive at the time of a [`snapshot()`].
+#[derive(Clone, Debug)]
+pub struct LiveObject {
+    /// Static class name. For engine objects, this is the class of the first `Gd` obtained, possibly a base class.
+    pub class_name: &'static str,
+
+    /// Instance ID, or `None` if the object was not yet fully constructed.
+    pub instance_id: Option<InstanceId>,
+
+    pub kind: ObjectKind,
+
+    /// Backtrace of the object's construction (or first sighting), if enabled via [`set_capture_backtraces()`].
+    pub backtrace: Option<String>,
+}
+
+impl Display for LiveObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let kind = match self.kind {
+            ObjectKind::User => "user",
+            ObjectKind::Engine => "engine",
+        };
+
+        match self.instance_id {
+            Some(id) => write!(f, "{} (instance ID {id}, {kind} class)", self.class_name)?,
+            None => write!(f, "{} (no instance ID, {kind} class)", self.class_name)?,
+        }
+
+        if let Some(backtrace) = &self.backtrace {
+            write!(f, "\n  created at:\n{backtrace}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Set of tracked objects alive at a certain point in time.
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    objects: BTreeMap<TrackKey, LiveObject>,
+}
+
+impl Snapshot {
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LiveObject> {
+        self.objects.values()
+    }
+
+    /// Compares this snapshot with a `later` one.
+    pub fn diff(&self, later: &Snapshot) -> SnapshotDiff {
+        let created = later
+            .objects
+            .iter()
+            .filter(|(key, _)| !self.objects.contains_key(key))
+            .map(|(_, obj)| obj.clone())
+            .collect();
+
+        let destroyed = self
+            .objects
+            .iter()
+            .filter(|(key, _)| !later.objects.contains_key(key))
+            .map(|(_, obj)| obj.clone())
+            .collect();
+
+        SnapshotDiff { created, destroyed }
+    }
+}
+
+/// Difference between two [`Snapshot`]s.
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotDiff {
+    /// Objects alive in the later snapshot, but not in the earlier one.
+    pub created: Vec<LiveObject>,
+
+    /// Objects alive in the earlier snapshot, but not in the later one.
+    pub destroyed: Vec<LiveObject>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.destroyed.is_empty()
+    }
+}
+
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for obj in &self.created {
+            writeln!(f, "+ {obj}")?;
+        }
+        for obj in &self.destroyed {
+            writeln!(f, "- {obj}")?;
+        }
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Recording (crate-internal)
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum TrackKey {
+    Storage(u64),
+    Engine(InstanceId),
+}
+
+/// Records construction of a user instance storage. Returns the token to pass to the other `on_storage_*` functions.
+pub(crate) fn on_storage_construct(class_name: &'static str) -> Option<u64> {
+    if !ENABLED {
+        return None;
+    }
+
+    let token = NEXT_STORAGE_TOKEN.fetch_add(1, Ordering::Relaxed);
+    let obj = LiveObject {
+        class_name,
+        instance_id: None,
+        kind: ObjectKind::User,
+        backtrace: capture_backtrace(),
+    };
+
+    LIVE_OBJECTS
+        .lock()
+        .unwrap()
+        .insert(TrackKey::Storage(token), obj);
+
+    Some(token)
+}
+
+/// Associates the instance ID of the Godot object with a recorded storage.
+pub(crate) fn on_storage_attached(token: Option<u64>, instance_id: InstanceId) {
+    if let Some(token) = token {
+        if let Some(obj) = LIVE_OBJECTS
+            .lock()
+            .unwrap()
+            .get_mut(&TrackKey::Storage(token))
+        {
+            obj.instance_id = Some(instance_id);
+        }
+    }
+}
+
+pub(crate) fn on_storage_destroy(token: Option<u64>) {
+    if let Some(token) = token {
+        LIVE_OBJECTS
+            .lock()
+            .unwrap()
+            .remove(&TrackKey::Storage(token));
+    }
+}
+
+/// Records an engine object, if engine objects are tracked. User objects are ignored, their storage is tracked instead.
+pub(crate) fn on_engine_object<T: GodotClass>(instance_id: InstanceId) {
+    if !ENABLED
+        || !TRACK_ENGINE_OBJECTS.load(Ordering::Relaxed)
+        || TypeId::of::<T::Declarer>() != TypeId::of::<dom::EngineDomain>()
+    {
+        return;
+    }
+
+    let mut live = LIVE_OBJECTS.lock().unwrap();
+    live.entry(TrackKey::Engine(instance_id))
+        .or_insert_with(|| LiveObject {
+            class_name: T::CLASS_NAME,
+            instance_id: Some(instance_id),
+            kind: ObjectKind::Engine,
+            backtrace: capture_backtrace(),
+        });
+}
+
+/// Prints all objects which are still alive. Called when the extension is unloaded.
+pub(crate) fn report_leaks() {
+    if !ENABLED {
+        return;
+    }
+
+    let live = snapshot();
+    if live.is_empty() {
+        return;
+    }
+
+    let objects: String = live.iter().map(|obj| format!("\n* {obj}")).collect();
+    crate::godot_warn!(
+        "{} object(s) still alive while unloading the extension:{}",
+        live.len(),
+        objects
+    );
+}
+
+fn is_alive(instance_id: InstanceId) -> bool {
+    let ptr = unsafe { interface_fn!(object_get_instance_from_id)(instance_id.to_u64()) };
+    !ptr.is_null()
+}
+
+#[cfg(feature = "leak-tracker")]
+fn capture_backtrace() -> Option<String> {
+    if CAPTURE_BACKTRACES.load(Ordering::Relaxed) {
+        Some(format!("{:?}", backtrace::Backtrace::new()))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(feature = "leak-tracker"))]
+fn capture_backtrace() -> Option<String> {
+    None
+}
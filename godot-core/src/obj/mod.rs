@@ -10,16 +10,31 @@
 //! * [`GodotClass`], which is implemented for every class that Godot can work with (either engine- or user-provided).
 //! * [`Gd`], a smart pointer that manages instances of Godot classes.
 
+mod access;
 mod as_arg;
 mod base;
+mod dyn_gd;
 mod gd;
 mod guards;
 mod instance_id;
+mod on_ready;
+mod owned;
+mod send_gd;
 mod traits;
+mod weak;
 
+pub mod leaks;
+
+pub use access::AccessError;
+pub(crate) use access::SAFEGUARDS;
 pub use as_arg::*;
 pub use base::*;
+pub use dyn_gd::*;
 pub use gd::*;
 pub use guards::*;
 pub use instance_id::*;
+pub use on_ready::*;
+pub use owned::*;
+pub use send_gd::*;
 pub use traits::*;
+pub use weak::*;
@@ -0,0 +1,132 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::ops::{Deref, DerefMut};
+
+use once_cell::unsync::OnceCell;
+
+use crate::builtin::NodePath;
+use crate::engine::{Node, NodeExt};
+use crate::log::godot_error;
+use crate::obj::{Base, Gd, GodotClass, Inherits, InstanceId};
+
+/// Child node of a user class, looked up by path once the node is ready.
+///
+/// Declared with `#[init(node = "Path/To/Child")]` on a field of a class with generated `init`. All such fields are
+/// resolved when the owning node receives `NOTIFICATION_READY`, which Godot delivers right after the `ready()` virtual.
+/// Accessing a field earlier (e.g. inside `ready()`) looks it up on the spot. Children are usually only available once
+/// the node has entered the scene tree, so the field should not be accessed before `ready()`:
+/// ```ignore
+/// #[derive(GodotClass)]
+/// #[class(init, base=Node2D)]
+/// struct Player {
+///     #[base]
+///     base: Base<Node2D>,
+///
+///     #[init(node = "Sprite")]
+///     sprite: OnReady<Sprite2D>,
+/// }
+/// ```
+///
+/// Dereferences to the resolved [`Gd<T>`].
+///
+/// # Panics
+/// On access before the node is ready, if the owning node has been freed, or no node of type `T` exists at the path.
+/// If the lookup fails on `NOTIFICATION_READY`, an error is logged instead, and accessing the field panics.
+pub struct OnReady<T: GodotClass> {
+    owner_id: InstanceId,
+    path: NodePath,
+    node: OnceCell<Gd<T>>,
+}
+
+impl<T> OnReady<T>
+where
+    T: GodotClass + Inherits<Node>,
+{
+    /// Refers to the node at `path`, relative to the object holding `base`.
+    pub fn node<B>(base: &Base<B>, path: impl Into<NodePath>) -> Self
+    where
+        B: GodotClass + Inherits<Node>,
+    {
+        Self {
+            owner_id: base.instance_id(),
+            path: path.into(),
+            node: OnceCell::new(),
+        }
+    }
+
+    /// Whether the node has already been looked up.
+    pub fn is_resolved(&self) -> bool {
+        self.node.get().is_some()
+    }
+
+    /// Looks up the node unless already done, logging an error on failure. Called on `NOTIFICATION_READY`.
+    #[doc(hidden)]
+    pub fn __resolve_on_ready(&self) {
+        if self.is_resolved() {
+            return;
+        }
+
+        // Runs inside an engine callback, where a panic would unwind across FFI
+        match self.lookup() {
+            Ok(node) => {
+                let _ = self.node.set(node);
+            }
+            Err(message) => godot_error!("{message}"),
+        }
+    }
+
+    fn resolve(&self) -> &Gd<T> {
+        self.node
+            .get_or_init(|| self.lookup().unwrap_or_else(|message| panic!("{message}")))
+    }
+
+    fn lookup(&self) -> Result<Gd<T>, String> {
+        let owner = Gd::<Node>::try_from_instance_id(self.owner_id)
+            .ok_or_else(|| format!("OnReady: owner of node '{}' was freed", self.path))?;
+
+        owner
+            .try_get_node_as::<T>(self.path.clone())
+            .ok_or_else(|| {
+                format!(
+                    "OnReady: there is no node of type {ty} at path '{path}'",
+                    ty = T::CLASS_NAME,
+                    path = self.path
+                )
+            })
+    }
+}
+
+impl<T> Deref for OnReady<T>
+where
+    T: GodotClass + Inherits<Node>,
+{
+    type Target = Gd<T>;
+
+    fn deref(&self) -> &Gd<T> {
+        self.resolve()
+    }
+}
+
+impl<T> DerefMut for OnReady<T>
+where
+    T: GodotClass + Inherits<Node>,
+{
+    fn deref_mut(&mut self) -> &mut Gd<T> {
+        self.resolve();
+        self.node.get_mut().expect("node resolved above")
+    }
+}
+
+impl<T: GodotClass> Debug for OnReady<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("OnReady")
+            .field("path", &self.path)
+            .field("resolved", &self.node.get().is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::ops::{Deref, DerefMut};
+
+use crate::obj::{mem, Gd, GodotClass, InstanceId};
+
+/// Scoped ownership of a manually-managed object, which is freed when the guard goes out of scope.
+///
+/// Obtained through [`Gd::into_owned()`]. Useful for temporary objects that would otherwise leak on early returns or panics.
+/// The guard dereferences to the underlying [`Gd<T>`], so the object can be used as usual.
+///
+/// When dropped, the object is destroyed as follows:
+/// * Nodes inside the scene tree are freed with `queue_free()`, i.e. at the end of the current frame.
+/// * All other objects are freed immediately, as with [`Gd::free()`].
+///
+/// The guard refuses to free the object (and silently does nothing) if:
+/// * The object has already been freed elsewhere, e.g. by another `Gd` or by GDScript.
+/// * The object is a node that has been added to another parent since ownership was taken -- the parent now owns it.
+///
+/// To give up ownership and keep the object alive, use [`release()`][Self::release].
+pub struct Owned<T>
+where
+    T: GodotClass,
+    T::Mem: mem::PossiblyManual,
+{
+    // Only None after release() or during drop
+    obj: Option<Gd<T>>,
+
+    // Parent when ownership was taken, to detect if the node has been re-parented since
+    parent_id: Option<InstanceId>,
+}
+
+impl<T> Owned<T>
+where
+    T: GodotClass,
+    T::Mem: mem::PossiblyManual,
+{
+    pub(crate) fn new(obj: Gd<T>) -> Self {
+        let parent_id = parent_of(&obj);
+        Self {
+            obj: Some(obj),
+            parent_id,
+        }
+    }
+
+    /// Gives up ownership, returning a plain `Gd<T>` which is no longer freed automatically.
+    pub fn release(mut self) -> Gd<T> {
+        self.obj.take().expect("object already released")
+    }
+
+    fn obj(&self) -> &Gd<T> {
+        self.obj.as_ref().expect("object already released")
+    }
+}
+
+impl<T> Deref for Owned<T>
+where
+    T: GodotClass,
+    T::Mem: mem::PossiblyManual,
+{
+    type Target = Gd<T>;
+
+    fn deref(&self) -> &Gd<T> {
+        self.obj()
+    }
+}
+
+impl<T> DerefMut for Owned<T>
+where
+    T: GodotClass,
+    T::Mem: mem::PossiblyManual,
+{
+    fn deref_mut(&mut self) -> &mut Gd<T> {
+        self.obj.as_mut().expect("object already released")
+    }
+}
+
+impl<T> Drop for Owned<T>
+where
+    T: GodotClass,
+    T::Mem: mem::PossiblyManual,
+{
+    fn drop(&mut self) {
+        let obj = match self.obj.take() {
+            Some(obj) => obj,
+            None => return, // released
+        };
+
+        // Freed elsewhere
+        if !obj.is_instance_valid() {
+            return;
+        }
+
+        let node_state = obj.as_node(|node| {
+            let parent_id = node
+                .get_parent()
+                .and_then(|parent| parent.instance_id_or_none());
+            (parent_id, node.is_inside_tree())
+        });
+
+        match node_state {
+            // Ownership was transferred to the new parent
+            Some((parent_id, _)) if parent_id.is_some() && parent_id != self.parent_id => {}
+            Some((_, true)) => {
+                obj.as_node(|node| node.queue_free());
+            }
+            _ => obj.free(),
+        }
+    }
+}
+
+impl<T> Debug for Owned<T>
+where
+    T: GodotClass,
+    T::Mem: mem::PossiblyManual,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("Owned").field(self.obj()).finish()
+    }
+}
+
+fn parent_of<T: GodotClass>(obj: &Gd<T>) -> Option<InstanceId> {
+    obj.as_node(|node| {
+        node.get_parent()
+            .and_then(|parent| parent.instance_id_or_none())
+    })
+    .flatten()
+}
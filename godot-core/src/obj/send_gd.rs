@@ -0,0 +1,91 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use crate::obj::dom::Domain as _;
+use crate::obj::{Gd, GodotClass};
+
+/// Hands over a [`Gd<T>`] to another thread.
+///
+/// # Thread safety model
+/// `Gd<T>` is neither `Send` nor `Sync`: an object belongs to the thread that created it, which is typically the main thread.
+/// For user classes, the owning thread is recorded in the instance storage. Binding the instance from another thread -- for example,
+/// when the engine calls a `#[func]` from a worker thread -- panics in debug builds, or with feature `safeguards`.
+/// [`Gd::try_bind()`] and [`Gd::try_bind_mut()`] return [`AccessError::WrongThread`][crate::obj::AccessError::WrongThread] instead.
+///
+/// `SendGd` moves an object to another thread in a controlled way:
+/// * Objects which are still shared cannot be sent: ref-counted objects must hold their only reference,
+///   and nodes must not be inside the scene tree (which is only accessible from the main thread).
+/// * [`into_inner()`][Self::into_inner] makes the receiving thread the new owner of the user instance (if any).
+///   Should the original thread still bind the instance through another `Gd`, it will hit the thread check.
+///
+/// Manually-managed objects outside the tree can have other `Gd` pointers on the sending thread; engine classes are not
+/// covered by the thread check, so it's your responsibility not to use those concurrently.
+///
+/// ```ignore
+/// let mesh = SendGd::new(ArrayMesh::new());
+///
+/// let handle = std::thread::spawn(move || {
+///     let mut mesh = mesh.into_inner();
+///     generate_terrain(&mut mesh);
+///     SendGd::new(mesh)
+/// });
+///
+/// let mesh = handle.join().unwrap().into_inner();
+/// ```
+pub struct SendGd<T: GodotClass> {
+    obj: Gd<T>,
+}
+
+// SAFETY: construction ensures that the object is not shared with other threads (see type-level docs).
+unsafe impl<T: GodotClass> Send for SendGd<T> {}
+
+impl<T: GodotClass> SendGd<T> {
+    /// Prepares `obj` to be sent to another thread.
+    ///
+    /// # Panics
+    /// * If the object is dead.
+    /// * If the object is ref-counted and other references to it exist.
+    /// * If the object is a node inside the scene tree.
+    #[track_caller]
+    pub fn new(obj: Gd<T>) -> Self {
+        let id = obj
+            .instance_id_or_none()
+            .unwrap_or_else(|| panic!("SendGd::new(): object of class {} is dead", T::CLASS_NAME));
+
+        if id.is_ref_counted() {
+            let count = obj.as_ref_counted(|refc| refc.get_reference_count());
+            assert_eq!(
+                count,
+                1,
+                "SendGd::new(): object {id} of class {} is still shared (reference count {count})",
+                T::CLASS_NAME
+            );
+        }
+
+        let inside_tree = obj.as_node(|node| node.is_inside_tree()).unwrap_or(false);
+        assert!(
+            !inside_tree,
+            "SendGd::new(): node {id} of class {} is inside the scene tree; remove it before sending",
+            T::CLASS_NAME
+        );
+
+        Self { obj }
+    }
+
+    /// Takes the object out of the wrapper on the receiving thread, which becomes its owner.
+    pub fn into_inner(self) -> Gd<T> {
+        T::Declarer::transfer_to_current_thread(&self.obj);
+        self.obj
+    }
+}
+
+impl<T: GodotClass> Debug for SendGd<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("SendGd").field(&self.obj).finish()
+    }
+}
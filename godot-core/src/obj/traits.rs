@@ -26,6 +26,15 @@ where
     type Mem: mem::Memory;
 
     const CLASS_NAME: &'static str;
+
+    /// Whether the class runs its virtual methods and notifications inside the editor, like `@tool` scripts.
+    ///
+    /// Only relevant for user classes, see `#[class(tool)]`.
+    const IS_TOOL: bool = false;
+
+    /// Looks up the `#[init(node)]` fields of a user class; called when the node receives `NOTIFICATION_READY`.
+    #[doc(hidden)]
+    fn __godot_resolve_on_ready(&self) {}
 }
 
 /// Unit impl only exists to represent "no base", and is used for exactly one class: `Object`.
@@ -78,6 +87,8 @@ pub trait EngineEnum: Copy {
 /// Capability traits, providing dedicated functionalities for Godot classes
 pub mod cap {
     use super::*;
+    use crate::builtin::meta::PropertyInfo;
+    use crate::builtin::{StringName, Variant};
 
     /// Trait for all classes that are constructible from the Godot engine.
     ///
@@ -94,16 +105,80 @@ pub mod cap {
         fn __godot_init(base: Base<Self::Base>) -> Self;
     }
 
-    /// Auto-implemented for `#[godot_api] impl MyClass` blocks
+    /// Auto-implemented for generic `#[godot_api] impl<T> MyClass<T>` blocks.
+    ///
+    /// Non-generic blocks register their methods directly, so a class can have any number of them.
     pub trait ImplementsGodotApi: GodotClass {
         #[doc(hidden)]
-        fn __register_methods();
+        fn __register_methods(_builder: &mut crate::builder::ClassBuilder<Self>);
+    }
+
+    /// Auto-implemented by `#[godot_alias]` for a concrete instantiation of a generic class, e.g. `MyClass<Enemy>`.
+    ///
+    /// Provides the class name under which this instantiation is registered in Godot.
+    pub trait ClassAlias {
+        #[doc(hidden)]
+        const CLASS_NAME: &'static str;
+    }
+
+    /// Auto-implemented for `#[derive(GodotClass)]` on generic structs.
+    ///
+    /// Such classes are not registered themselves; each `#[godot_alias]` registers one instantiation.
+    pub trait GenericClass: GodotClass {
+        #[doc(hidden)]
+        fn __class_def() -> crate::PluginComponent;
+    }
+
+    /// Auto-implemented for generic `#[godot_api] impl<T> NodeVirtual for MyClass<T>` blocks (or `GodotExt`).
+    ///
+    /// Provides the virtual method callbacks, which each `#[godot_alias]` registers for its instantiation.
+    pub trait GenericVirtuals: GodotClass {
+        #[doc(hidden)]
+        fn __virtuals() -> crate::PluginComponent;
     }
 
-    /// Auto-implemented for `#[godot_api] impl GodotExt for MyClass` blocks
+    /// Auto-implemented for `#[godot_api] impl GodotExt for MyClass` and `#[godot_api] impl NodeVirtual for MyClass` blocks
     pub trait ImplementsGodotExt: GodotClass {
         #[doc(hidden)]
         fn __virtual_call(_name: &str) -> sys::GDNativeExtensionClassCallVirtual;
+
+        #[doc(hidden)]
+        fn __register_class(_builder: &mut crate::builder::ClassBuilder<Self>) {}
+
+        /// Only called if the class provides a `to_string` method.
+        #[doc(hidden)]
+        fn __godot_to_string(&self) -> crate::builtin::GodotString {
+            unreachable!("to_string() not implemented for {}", Self::CLASS_NAME)
+        }
+
+        /// Only called if the class provides an `on_notification` method.
+        #[doc(hidden)]
+        fn __godot_notification(&mut self, _what: i32) {}
+
+        #[doc(hidden)]
+        fn __godot_get_property(&self, _name: &StringName) -> Option<Variant> {
+            None
+        }
+
+        #[doc(hidden)]
+        fn __godot_set_property(&mut self, _name: &StringName, _value: Variant) -> bool {
+            false
+        }
+
+        #[doc(hidden)]
+        fn __godot_property_list(&self) -> Vec<PropertyInfo> {
+            Vec::new()
+        }
+
+        #[doc(hidden)]
+        fn __godot_property_can_revert(&self, _name: &StringName) -> bool {
+            false
+        }
+
+        #[doc(hidden)]
+        fn __godot_property_get_revert(&self, _name: &StringName) -> Option<Variant> {
+            None
+        }
     }
 }
 
@@ -124,6 +199,12 @@ pub mod dom {
         where
             T: GodotClass<Declarer = Self>,
             F: FnOnce(&mut T) -> R;
+
+        /// Makes the current thread the owner of the obj's user instance, if any.
+        #[doc(hidden)]
+        fn transfer_to_current_thread<T>(obj: &Gd<T>)
+        where
+            T: GodotClass<Declarer = Self>;
     }
 
     pub enum EngineDomain {}
@@ -136,6 +217,13 @@ pub mod dom {
         {
             closure(obj.deref_mut())
         }
+
+        fn transfer_to_current_thread<T>(_obj: &Gd<T>)
+        where
+            T: GodotClass<Declarer = Self>,
+        {
+            // Engine objects are not tracked per thread
+        }
     }
 
     pub enum UserDomain {}
@@ -149,6 +237,13 @@ pub mod dom {
             let mut guard = obj.bind_mut();
             closure(guard.deref_mut())
         }
+
+        fn transfer_to_current_thread<T>(obj: &Gd<T>)
+        where
+            T: GodotClass<Declarer = Self>,
+        {
+            obj.storage().transfer_to_current_thread()
+        }
     }
 }
 
@@ -210,27 +305,34 @@ pub mod mem {
     }
 
     /// Memory managed through Godot reference counter, if present; otherwise manual.
-    /// This is used only for `Object` classes.
+    /// This is used for `Object` and user classes directly inheriting `Object`, whose dynamic type may or may not be `RefCounted`.
+    ///
+    /// The decision is made at runtime, for each operation, based on the dynamic type encoded in the instance ID
+    /// (see [`InstanceId::is_ref_counted()`][crate::obj::InstanceId::is_ref_counted]). This needs no engine round-trip
+    /// and works even if the object is no longer alive.
+    ///
+    /// Since a `Gd` always owns one reference (if ref-counted) independently of its static type, casting between
+    /// a `Gd<Object>` and a `Gd` with static memory strategy transfers that reference as-is.
     pub struct DynamicRefCount {}
     impl Sealed for DynamicRefCount {}
     impl Memory for DynamicRefCount {
         fn maybe_init_ref<T: GodotClass>(obj: &Gd<T>) {
             out!("  Dyn::init  <{}>", std::any::type_name::<T>());
-            if obj.instance_id().is_ref_counted() {
+            if obj.is_dynamic_ref_counted() {
                 StaticRefCount::maybe_init_ref(obj);
             }
         }
 
         fn maybe_inc_ref<T: GodotClass>(obj: &Gd<T>) {
             out!("  Dyn::inc   <{}>", std::any::type_name::<T>());
-            if obj.instance_id().is_ref_counted() {
+            if obj.is_dynamic_ref_counted() {
                 StaticRefCount::maybe_inc_ref(obj);
             }
         }
 
         fn maybe_dec_ref<T: GodotClass>(obj: &Gd<T>) -> bool {
             out!("  Dyn::dec   <{}>", std::any::type_name::<T>());
-            if obj.instance_id().is_ref_counted() {
+            if obj.is_dynamic_ref_counted() {
                 StaticRefCount::maybe_dec_ref(obj)
             } else {
                 false
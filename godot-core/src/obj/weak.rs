@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+
+use godot_ffi as sys;
+use sys::interface_fn;
+
+use crate::engine;
+use crate::obj::{Gd, GodotClass, InstanceId};
+
+/// Weak reference to a Godot object, obtained through [`Gd::downgrade()`].
+///
+/// A `WeakGd` only stores the object's instance ID, together with its static type `T` as a type tag. It does not keep the
+/// object alive: for reference-counted types, it does not contribute to the reference count. This makes it suitable
+/// for caches and back-pointers (e.g. child to parent), which would otherwise create reference cycles that are never freed.
+///
+/// To access the object, convert it back to a strong reference using [`WeakGd::upgrade()`].
+pub struct WeakGd<T: GodotClass> {
+    instance_id: InstanceId,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T: GodotClass> WeakGd<T> {
+    pub(crate) fn from_instance_id(instance_id: InstanceId) -> Self {
+        Self {
+            instance_id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Tries to obtain a strong reference to the object.
+    ///
+    /// Returns `None` if the object has been destroyed, is in the process of being destroyed, or its dynamic type is no
+    /// longer compatible with `T`. For reference-counted objects, a successful upgrade increments the reference count.
+    pub fn upgrade(&self) -> Option<Gd<T>> {
+        // SAFETY: Godot looks up ID in ObjectDB and returns null if not found
+        let ptr = unsafe { interface_fn!(object_get_instance_from_id)(self.instance_id.to_u64()) };
+        if ptr.is_null() {
+            return None;
+        }
+
+        // Not yet owning: reference count has not been incremented
+        let untyped = unsafe { Gd::<engine::Object>::from_obj_sys(ptr) };
+
+        if self.instance_id.is_ref_counted() {
+            // reference() only increments if the count is still positive. A ref-counted object whose count already
+            // dropped to zero is about to be destroyed, but may still be registered in the ObjectDB.
+            let success = untyped.as_ref_counted(|refc| refc.reference());
+            if !success {
+                std::mem::forget(untyped);
+                return None;
+            }
+        }
+
+        // On failure, dropping the Gd<Object> releases the reference acquired above
+        untyped.owned_cast::<T>().ok()
+    }
+
+    /// Instance ID of the referred-to object. The object is not guaranteed to be alive.
+    pub fn instance_id(&self) -> InstanceId {
+        self.instance_id
+    }
+}
+
+impl<T: GodotClass> Clone for WeakGd<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: GodotClass> Copy for WeakGd<T> {}
+
+impl<T: GodotClass> Debug for WeakGd<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "WeakGd {{ id: {}, class: {} }}",
+            self.instance_id,
+            T::CLASS_NAME
+        )
+    }
+}
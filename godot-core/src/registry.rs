@@ -8,33 +8,42 @@
 
 use crate::obj::*;
 use crate::private::as_storage;
-use crate::storage::InstanceStorage;
+use crate::storage::{BindSite, InstanceStorage};
 use godot_ffi as sys;
 
 use sys::interface_fn;
 
-use crate::bind::GodotExt;
 use crate::builtin::meta::ClassName;
-use crate::builtin::StringName;
+use crate::builtin::{StringName, Variant};
+use crate::log::godot_error;
 use crate::out;
 use std::any::Any;
-use std::collections::HashMap;
-use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::mem::ManuallyDrop;
 use std::ptr;
+use std::sync::atomic::{self, AtomicBool};
 
 #[derive(Debug)]
 pub struct ClassPlugin {
     pub class_name: &'static str,
+
+    /// Rust type declaring the plugin, as returned by `std::any::type_name()`
+    pub rust_type: &'static str,
+
+    /// File and line of the macro invocation, e.g. `src/player.rs:12`
+    pub source_location: &'static str,
+
     pub component: PluginComponent,
 }
 
 /// Type-erased function obj, holding a `register_class` function.
+///
+/// The function creates the `ClassBuilder<T>` itself, since the class type is not known at the point of invocation.
 #[derive(Copy, Clone)]
 pub struct ErasedRegisterFn {
-    // Wrapper needed because Debug can't be derived on function pointers with reference parameters, so this won't work:
-    // pub type ErasedRegisterFn = fn(&mut dyn std::any::Any);
-    // (see https://stackoverflow.com/q/53380040)
-    pub raw: fn(&mut dyn Any),
+    // Wrapper to print the function address in Debug
+    pub raw: fn(),
 }
 
 impl Debug for ErasedRegisterFn {
@@ -60,9 +69,28 @@ pub enum PluginComponent {
             _class_user_data: *mut std::ffi::c_void,
             instance: sys::GDExtensionClassInstancePtr,
         ),
+
+        /// Godot low-level `notification` function, which resolves `#[init(node)]` fields on `NOTIFICATION_READY`.
+        /// Superseded by a user-defined `on_notification`, which resolves them as well.
+        generated_on_notification_fn: Option<
+            unsafe extern "C" fn(
+                p_instance: sys::GDExtensionClassInstancePtr, //
+                p_what: i32,
+            ),
+        >,
+
+        /// Whether the class is registered, but cannot be instantiated from Godot
+        is_abstract: bool,
+
+        /// Whether the class is hidden from editor dialogs like "Create New Node"
+        is_hidden: bool,
+
+        /// Whether Godot may create instances, e.g. through `MyClass.new()` in GDScript.
+        /// False for `#[class(abstract)]` and `#[class(no_init)]`.
+        is_instantiable: bool,
     },
 
-    /// Collected from `#[godot_api] impl MyClass`
+    /// Collected from `#[godot_api] impl MyClass`, once per `impl` block
     UserMethodBinds {
         /// Callback to library-generated function which registers functions in the `impl`
         ///
@@ -70,7 +98,13 @@ pub enum PluginComponent {
         generated_register_fn: ErasedRegisterFn,
     },
 
-    /// Collected from `#[godot_api] impl GodotExt for MyClass`
+    /// Collected from `#[derive(GodotEnum)]` with `#[godot_enum(class = MyClass)]`
+    UserEnum {
+        /// Callback to library-generated function which registers the enumerators as integer constants
+        generated_register_fn: ErasedRegisterFn,
+    },
+
+    /// Collected from `#[godot_api] impl GodotExt for MyClass` or `#[godot_api] impl NodeVirtual for MyClass`
     UserVirtuals {
         /// Callback to user-defined `register_class` function
         user_register_fn: Option<ErasedRegisterFn>,
@@ -91,6 +125,29 @@ pub enum PluginComponent {
             ),
         >,
 
+        /// User-defined `on_notification` function
+        user_on_notification_fn: Option<
+            unsafe extern "C" fn(
+                p_instance: sys::GDExtensionClassInstancePtr, //
+                p_what: i32,
+            ),
+        >,
+
+        /// User-defined `get_property` function
+        user_get_property_fn: sys::GDNativeExtensionClassGet,
+
+        /// User-defined `set_property` function
+        user_set_property_fn: sys::GDNativeExtensionClassSet,
+
+        /// User-defined `property_list` function
+        user_property_list_fn: sys::GDNativeExtensionClassGetPropertyList,
+
+        /// User-defined `property_can_revert` function
+        user_property_can_revert_fn: sys::GDNativeExtensionClassPropertyCanRevert,
+
+        /// User-defined `property_get_revert` function
+        user_property_get_revert_fn: sys::GDNativeExtensionClassPropertyGetRevert,
+
         /// Callback for other virtuals
         get_virtual_fn: unsafe extern "C" fn(
             p_userdata: *mut std::os::raw::c_void,
@@ -101,21 +158,43 @@ pub enum PluginComponent {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+/// Origin of a plugin, used in error messages
+#[derive(Copy, Clone, Debug)]
+struct PluginSource {
+    rust_type: &'static str,
+    location: &'static str,
+}
+
+impl Display for PluginSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "`{}` ({})", self.rust_type, self.location)
+    }
+}
+
 #[derive(Debug)]
 struct ClassRegistrationInfo {
-    class_name: ClassName,
-    parent_class_name: Option<ClassName>,
-    generated_register_fn: Option<ErasedRegisterFn>,
+    // Plain strings until registration, so that validation does not need the engine
+    class_name: &'static str,
+    parent_class_name: Option<&'static str>,
+
+    /// Sources of `ClassDef` components; exactly one for a valid class
+    definitions: Vec<PluginSource>,
+
+    /// Sources of all other components
+    contributors: Vec<PluginSource>,
+
+    generated_register_fns: Vec<ErasedRegisterFn>,
+    enum_register_fns: Vec<ErasedRegisterFn>,
     user_register_fn: Option<ErasedRegisterFn>,
+    is_instantiable: bool,
     godot_params: sys::GDNativeExtensionClassCreationInfo,
 }
 
-pub fn register_class<T: GodotExt + cap::GodotInit + cap::ImplementsGodotExt>() {
+pub fn register_class<T: cap::GodotInit + cap::ImplementsGodotExt>() {
     // TODO: provide overloads with only some trait impls
 
     out!("Manually register class {}", std::any::type_name::<T>());
-    let class_name = ClassName::new::<T>();
-
+    cache_editor_hint();
     let godot_params = sys::GDNativeExtensionClassCreationInfo {
         to_string_func: Some(callbacks::to_string::<T>),
         reference_func: Some(callbacks::reference::<T>),
@@ -129,12 +208,16 @@ pub fn register_class<T: GodotExt + cap::GodotInit + cap::ImplementsGodotExt>()
     };
 
     register_class_raw(ClassRegistrationInfo {
-        class_name,
-        parent_class_name: Some(ClassName::new::<T::Base>()),
-        generated_register_fn: None,
+        class_name: T::CLASS_NAME,
+        parent_class_name: Some(T::Base::CLASS_NAME),
+        definitions: vec![],
+        contributors: vec![],
+        generated_register_fns: vec![],
+        enum_register_fns: vec![],
         user_register_fn: Some(ErasedRegisterFn {
             raw: callbacks::register_class_by_builder::<T>,
         }),
+        is_instantiable: true,
         godot_params,
     });
 }
@@ -142,28 +225,45 @@ pub fn register_class<T: GodotExt + cap::GodotInit + cap::ImplementsGodotExt>()
 /// Lets Godot know about all classes that have self-registered through the plugin system.
 pub fn auto_register_classes() {
     out!("Auto-register classes...");
+    cache_editor_hint();
 
     // Note: many errors are already caught by the compiler, before this runtime validation even takes place:
     // * missing #[derive(GodotClass)] or impl GodotClass for T
     // * duplicate impl GodotInit for T
     //
+    // The remaining ones (duplicate names, manual impl GodotClass without definition, names of engine classes) are
+    // reported by validate_classes() and before registering each class.
 
-    let mut map = HashMap::<ClassName, ClassRegistrationInfo>::new();
+    let mut map = HashMap::<&'static str, ClassRegistrationInfo>::new();
 
     crate::private::iterate_plugins(|elem: &ClassPlugin| {
         //out!("* Plugin: {elem:#?}");
 
-        let name = ClassName::from_static(elem.class_name);
         let class_info = map
-            .entry(name.clone())
-            .or_insert_with(|| default_registration_info(name));
-
-        fill_class_info(elem.component.clone(), class_info);
+            .entry(elem.class_name)
+            .or_insert_with(|| default_registration_info(elem.class_name));
+
+        let source = PluginSource {
+            rust_type: elem.rust_type,
+            location: elem.source_location,
+        };
+        fill_class_info(elem.component.clone(), source, class_info);
     });
 
     //out!("Class-map: {map:#?}");
 
-    for info in map.into_values() {
+    let (classes, errors) = validate_classes(map);
+    for error in errors {
+        godot_error!("{error}");
+    }
+
+    for info in classes {
+        // Godot rejects such classes, but methods and properties would end up on the engine class
+        if shadows_engine_class(&info) {
+            godot_error!("{}", shadowing_error(&info));
+            continue;
+        }
+
         out!("Register class:   {}", info.class_name);
         register_class_raw(info);
     }
@@ -171,7 +271,19 @@ pub fn auto_register_classes() {
     out!("All classes auto-registered.");
 }
 
-fn fill_class_info(component: PluginComponent, c: &mut ClassRegistrationInfo) {
+/// Whether the engine runs in the editor; does not change after startup. Read by callbacks, which can be invoked very often.
+static IS_EDITOR_HINT: AtomicBool = AtomicBool::new(false);
+
+fn cache_editor_hint() {
+    let is_editor = crate::engine::Engine::singleton().is_editor_hint();
+    IS_EDITOR_HINT.store(is_editor, atomic::Ordering::Relaxed);
+}
+
+fn fill_class_info(
+    component: PluginComponent,
+    source: PluginSource,
+    c: &mut ClassRegistrationInfo,
+) {
     // out!("|   reg (before):    {c:?}");
     // out!("|   comp:            {component:?}");
     match component {
@@ -179,30 +291,75 @@ fn fill_class_info(component: PluginComponent, c: &mut ClassRegistrationInfo) {
             base_class_name,
             generated_create_fn,
             free_fn,
+            generated_on_notification_fn,
+            is_abstract,
+            is_hidden,
+            is_instantiable,
         } => {
-            c.parent_class_name = Some(ClassName::from_static(base_class_name));
+            c.definitions.push(source);
+            if c.definitions.len() > 1 {
+                // Duplicate class name, reported by validate_classes()
+                return;
+            }
+
+            c.is_instantiable = is_instantiable;
+            c.parent_class_name = Some(base_class_name);
+            c.godot_params.is_abstract = is_abstract as sys::GDNativeBool;
+            // Godot calls these "virtual" classes: they remain instantiable, but the editor's "Create New Node" dialog skips them
+            // (ClassDB::is_virtual() in CreateDialog). Not to be confused with abstract classes, which have no constructor.
+            c.godot_params.is_virtual = is_hidden as sys::GDNativeBool;
             fill_into(
                 &mut c.godot_params.create_instance_func,
                 generated_create_fn,
             );
             c.godot_params.free_instance_func = Some(free_fn);
+            if c.godot_params.notification_func.is_none() {
+                c.godot_params.notification_func = generated_on_notification_fn;
+            }
         }
 
         PluginComponent::UserMethodBinds {
             generated_register_fn,
         } => {
-            c.generated_register_fn = Some(generated_register_fn);
+            c.contributors.push(source);
+            // A class may have any number of inherent #[godot_api] blocks, e.g. spread across files
+            c.generated_register_fns.push(generated_register_fn);
+        }
+
+        PluginComponent::UserEnum {
+            generated_register_fn,
+        } => {
+            c.contributors.push(source);
+            c.enum_register_fns.push(generated_register_fn);
         }
 
         PluginComponent::UserVirtuals {
             user_register_fn,
             user_create_fn,
             user_to_string_fn,
+            user_on_notification_fn,
+            user_get_property_fn,
+            user_set_property_fn,
+            user_property_list_fn,
+            user_property_can_revert_fn,
+            user_property_get_revert_fn,
             get_virtual_fn,
         } => {
+            c.contributors.push(source);
             c.user_register_fn = user_register_fn;
             fill_into(&mut c.godot_params.create_instance_func, user_create_fn);
             c.godot_params.to_string_func = user_to_string_fn;
+            if user_on_notification_fn.is_some() {
+                c.godot_params.notification_func = user_on_notification_fn;
+            }
+            c.godot_params.get_func = user_get_property_fn;
+            c.godot_params.set_func = user_set_property_fn;
+            c.godot_params.property_can_revert_func = user_property_can_revert_fn;
+            c.godot_params.property_get_revert_func = user_property_get_revert_fn;
+            if user_property_list_fn.is_some() {
+                c.godot_params.get_property_list_func = user_property_list_fn;
+                c.godot_params.free_property_list_func = Some(callbacks::free_property_list);
+            }
             c.godot_params.get_virtual_func = Some(get_virtual_fn);
         }
     }
@@ -210,6 +367,78 @@ fn fill_class_info(component: PluginComponent, c: &mut ClassRegistrationInfo) {
     // out!();
 }
 
+/// Returns the valid classes, sorted by name, and error messages for the invalid ones.
+///
+/// A class is invalid if it has no or multiple definitions (`#[derive(GodotClass)]`), or if its name shadows an engine
+/// class.
+///
+/// Rust classes cannot inherit from each other: base classes are always engine classes. A class whose name is used as a
+/// base therefore takes the name of an engine class, e.g. `#[class(rename = "Node", base = Node)]`. Shadowing engine
+/// classes that are not used as a base is detected when registering, see [`shadows_engine_class()`].
+fn validate_classes(
+    mut map: HashMap<&'static str, ClassRegistrationInfo>,
+) -> (Vec<ClassRegistrationInfo>, Vec<String>) {
+    let mut errors = vec![];
+    let mut invalid = HashSet::<&'static str>::new();
+
+    let bases: HashSet<&'static str> = map
+        .values()
+        .filter_map(|info| info.parent_class_name)
+        .collect();
+
+    for info in map.values() {
+        match info.definitions.as_slice() {
+            [] => {
+                errors.push(format!(
+                    "Class `{}` is not defined; missing #[derive(GodotClass)] for {}",
+                    info.class_name,
+                    join_sources(&info.contributors)
+                ));
+                invalid.insert(info.class_name);
+            }
+            [_] if bases.contains(info.class_name) => {
+                errors.push(shadowing_error(info));
+                invalid.insert(info.class_name);
+            }
+            [_] => {}
+            definitions => {
+                errors.push(format!(
+                    "Class `{}` is defined multiple times, by {}",
+                    info.class_name,
+                    join_sources(definitions)
+                ));
+                invalid.insert(info.class_name);
+            }
+        }
+    }
+
+    // Sort errors as well, since map iteration order is random
+    errors.sort();
+
+    map.retain(|name, _| !invalid.contains(name));
+    let mut valid: Vec<ClassRegistrationInfo> = map.into_values().collect();
+    valid.sort_by_key(|info| info.class_name);
+    (valid, errors)
+}
+
+/// Whether Godot already knows a class named like `info`, i.e. an engine class or one of another extension.
+fn shadows_engine_class(info: &ClassRegistrationInfo) -> bool {
+    crate::engine::ClassDB::singleton().class_exists(StringName::from(info.class_name))
+}
+
+fn shadowing_error(info: &ClassRegistrationInfo) -> String {
+    format!(
+        "Class `{}` shadows an engine class of the same name; declared by {}",
+        info.class_name,
+        join_sources(&info.definitions)
+    )
+}
+
+fn join_sources(sources: &[PluginSource]) -> String {
+    let sources: Vec<String> = sources.iter().map(|source| source.to_string()).collect();
+    sources.join(", ")
+}
+
 fn fill_into<T>(dst: &mut Option<T>, src: Option<T>) {
     match (dst, src) {
         (dst @ None, src) => *dst = src,
@@ -218,13 +447,20 @@ fn fill_into<T>(dst: &mut Option<T>, src: Option<T>) {
     }
 }
 
-fn register_class_raw(info: ClassRegistrationInfo) {
+fn register_class_raw(mut info: ClassRegistrationInfo) {
+    // Such classes can still be constructed from Rust (through GodotInit or Gd::with_base), but not from Godot.
+    // The create function may also come from an init() in the virtual trait impl, so it is only discarded here.
+    if !info.is_instantiable {
+        info.godot_params.create_instance_func = None;
+    }
+
     // First register class...
 
-    let class_name = info.class_name;
-    let parent_class_name = info
-        .parent_class_name
-        .expect("class defined (parent_class_name)");
+    let class_name = ClassName::from_static(info.class_name);
+    let parent_class_name = ClassName::from_static(
+        info.parent_class_name
+            .expect("class defined (parent_class_name)"),
+    );
 
     unsafe {
         interface_fn!(classdb_register_extension_class)(
@@ -240,25 +476,114 @@ fn register_class_raw(info: ClassRegistrationInfo) {
 
     // ...then custom symbols
 
-    //let mut class_builder = crate::builder::ClassBuilder::<?>::new();
-    let mut class_builder = 0; // TODO dummy argument; see callbacks
-
     // First call generated (proc-macro) registration function, then user-defined one.
     // This mimics the intuition that proc-macros are running "before" normal runtime code.
-    if let Some(register_fn) = info.generated_register_fn {
-        (register_fn.raw)(&mut class_builder);
+    for register_fn in info.generated_register_fns {
+        (register_fn.raw)();
+    }
+    for register_fn in info.enum_register_fns {
+        (register_fn.raw)();
     }
     if let Some(register_fn) = info.user_register_fn {
-        (register_fn.raw)(&mut class_builder);
+        (register_fn.raw)();
+    }
+}
+
+/// Registers an integer constant of a class, optionally as enumerator of `enum_name` (empty for plain constants).
+#[doc(hidden)]
+pub fn register_integer_constant(
+    class_name: &ClassName,
+    enum_name: &str,
+    constant_name: &str,
+    value: i64,
+    is_bitfield: bool,
+) {
+    out!("   Register constant:   {}::{}", class_name, constant_name);
+
+    let enum_name = StringName::from(enum_name);
+    let constant_name = StringName::from(constant_name);
+    unsafe {
+        interface_fn!(classdb_register_extension_class_integer_constant)(
+            sys::get_library(),
+            class_name.string_sys(),
+            enum_name.string_sys(),
+            constant_name.string_sys(),
+            value,
+            is_bitfield as sys::GDNativeBool,
+        );
+    }
+}
+
+/// Looks up the optional generic `#[godot_api]` blocks of a generic class, for each `#[godot_alias]` instantiation.
+///
+/// A generic class may or may not have a generic inherent block and a generic trait impl, but the alias cannot know.
+/// Uses autoref specialization: for `(&GenericProbe::<A>(PhantomData)).__method_binds()`, the impl on `GenericProbe<A>`
+/// is preferred if `A` implements the trait, and the fallback on `&GenericProbe<A>` is used otherwise.
+// Re-exported to crate::private
+pub mod generic_probe {
+    use super::*;
+    use std::marker::PhantomData;
+
+    pub struct GenericProbe<T>(pub PhantomData<T>);
+
+    pub trait ProbeMethodBinds {
+        fn __method_binds(&self) -> PluginComponent;
+    }
+
+    impl<T: cap::ImplementsGodotApi> ProbeMethodBinds for GenericProbe<T> {
+        fn __method_binds(&self) -> PluginComponent {
+            PluginComponent::UserMethodBinds {
+                generated_register_fn: ErasedRegisterFn {
+                    raw: callbacks::register_user_binds::<T>,
+                },
+            }
+        }
+    }
+
+    pub trait ProbeMethodBindsFallback {
+        fn __method_binds(&self) -> PluginComponent;
+    }
+
+    impl<T> ProbeMethodBindsFallback for &GenericProbe<T> {
+        fn __method_binds(&self) -> PluginComponent {
+            nothing_to_register()
+        }
+    }
+
+    pub trait ProbeVirtuals {
+        fn __virtuals(&self) -> PluginComponent;
+    }
+
+    impl<T: cap::GenericVirtuals> ProbeVirtuals for GenericProbe<T> {
+        fn __virtuals(&self) -> PluginComponent {
+            T::__virtuals()
+        }
+    }
+
+    pub trait ProbeVirtualsFallback {
+        fn __virtuals(&self) -> PluginComponent;
+    }
+
+    impl<T> ProbeVirtualsFallback for &GenericProbe<T> {
+        fn __virtuals(&self) -> PluginComponent {
+            nothing_to_register()
+        }
+    }
+
+    fn nothing_to_register() -> PluginComponent {
+        PluginComponent::UserMethodBinds {
+            generated_register_fn: ErasedRegisterFn { raw: || {} },
+        }
     }
 }
 
 // Re-exported to crate::private
 pub mod callbacks {
     use super::*;
-    use crate::bind::GodotExt;
     use crate::builder::ClassBuilder;
+    use crate::engine::notify::NodeNotification;
     use crate::obj::Base;
+    use std::mem::MaybeUninit;
 
     pub unsafe extern "C" fn create<T: cap::GodotInit>(
         _class_userdata: *mut std::ffi::c_void,
@@ -282,6 +607,11 @@ pub mod callbacks {
 
         let user_instance = make_user_instance(base);
         let instance = InstanceStorage::<T>::construct(user_instance);
+        if let Some(id) =
+            InstanceId::try_from_u64(unsafe { interface_fn!(object_get_instance_id)(base_ptr) })
+        {
+            instance.on_attached(id);
+        }
         let instance_ptr = instance.into_raw();
         let instance_ptr = instance_ptr as *mut std::ffi::c_void; // TODO GDExtensionClassInstancePtr
 
@@ -314,13 +644,18 @@ pub mod callbacks {
         _class_user_data: *mut std::ffi::c_void,
         name: sys::GDNativeStringNamePtr,
     ) -> sys::GDNativeExtensionClassCallVirtual {
-        let method_name = StringName::from_string_sys(name);
+        if !is_script_active::<T>() {
+            return None;
+        }
+
+        // Name is owned by Godot and must not be destroyed here
+        let method_name = ManuallyDrop::new(StringName::from_string_sys(name));
         let method_name = method_name.to_string();
 
         T::__virtual_call(method_name.as_str())
     }
 
-    pub unsafe extern "C" fn to_string<T: GodotExt>(
+    pub unsafe extern "C" fn to_string<T: cap::ImplementsGodotExt>(
         instance: sys::GDExtensionClassInstancePtr,
         _is_valid: *mut sys::GDNativeBool,
         out_string: sys::GDNativeStringPtr,
@@ -329,14 +664,279 @@ pub mod callbacks {
         // is_valid output parameter thus not needed.
 
         let storage = as_storage::<T>(instance);
-        let instance = storage.get();
-        let string = <T as GodotExt>::to_string(&*instance);
+        let instance = storage.get(BindSite::Method("to_string"));
+        let string = T::__godot_to_string(&*instance);
 
         // Transfer ownership to Godot, disable destructor
         string.write_string_sys(out_string);
         std::mem::forget(string);
     }
 
+    pub unsafe extern "C" fn on_notification<T: cap::ImplementsGodotExt>(
+        instance: sys::GDExtensionClassInstancePtr,
+        what: i32,
+    ) {
+        // Notifications sent while the base object is constructed (e.g. NOTIFICATION_POSTINITIALIZE) arrive before
+        // the user instance is attached
+        if instance.is_null() || !is_script_active::<T>() {
+            return;
+        }
+
+        // The engine may notify an instance that is already bound, e.g. from a base call inside a `&mut self` method;
+        // panicking here would unwind across FFI
+        let storage = as_storage::<T>(instance);
+        let mut instance = match storage.try_get_mut(BindSite::Method("on_notification")) {
+            Ok(instance) => instance,
+            Err(conflict) => {
+                godot_error!("notification {what} not delivered: {conflict}");
+                return;
+            }
+        };
+
+        if what == i32::from(NodeNotification::READY) {
+            T::__godot_resolve_on_ready(&*instance);
+        }
+        T::__godot_notification(&mut *instance, what);
+    }
+
+    /// Notification callback for classes with `#[init(node)]` fields, but without a user-defined `on_notification`.
+    pub unsafe extern "C" fn on_ready_notification<T: GodotClass>(
+        instance: sys::GDExtensionClassInstancePtr,
+        what: i32,
+    ) {
+        if what != i32::from(NodeNotification::READY)
+            || instance.is_null()
+            || !is_script_active::<T>()
+        {
+            return;
+        }
+
+        let storage = as_storage::<T>(instance);
+        match storage.try_get(BindSite::Method("on_notification")) {
+            Ok(instance) => T::__godot_resolve_on_ready(&*instance),
+            Err(conflict) => godot_error!("#[init(node)] fields not resolved: {conflict}"),
+        }
+    }
+
+    pub unsafe extern "C" fn get_property<T: cap::ImplementsGodotExt>(
+        instance: sys::GDExtensionClassInstancePtr,
+        name: sys::GDNativeStringNamePtr,
+        out_value: sys::GDNativeVariantPtr,
+    ) -> sys::GDNativeBool {
+        // Name is owned by Godot and must not be destroyed here
+        let name = ManuallyDrop::new(StringName::from_string_sys(name));
+
+        let storage = as_storage::<T>(instance);
+        let instance = match storage.try_get(BindSite::Method("get_property")) {
+            Ok(instance) => instance,
+            Err(conflict) => {
+                godot_error!("{conflict}");
+                return false as sys::GDNativeBool;
+            }
+        };
+
+        match T::__godot_get_property(&*instance, &name) {
+            Some(value) => {
+                // Transfer ownership to Godot, disable destructor
+                value.write_var_sys(out_value);
+                std::mem::forget(value);
+                true as sys::GDNativeBool
+            }
+            None => false as sys::GDNativeBool,
+        }
+    }
+
+    pub unsafe extern "C" fn set_property<T: cap::ImplementsGodotExt>(
+        instance: sys::GDExtensionClassInstancePtr,
+        name: sys::GDNativeStringNamePtr,
+        value: sys::GDNativeVariantPtr,
+    ) -> sys::GDNativeBool {
+        // Name and value are owned by Godot and must not be destroyed here
+        let name = ManuallyDrop::new(StringName::from_string_sys(name));
+        let value = ManuallyDrop::new(Variant::from_var_sys(value));
+
+        let storage = as_storage::<T>(instance);
+        let mut instance = match storage.try_get_mut(BindSite::Method("set_property")) {
+            Ok(instance) => instance,
+            Err(conflict) => {
+                godot_error!("{conflict}");
+                return false as sys::GDNativeBool;
+            }
+        };
+
+        T::__godot_set_property(&mut *instance, &name, (*value).clone()) as sys::GDNativeBool
+    }
+
+    /// Property list handed out to Godot, retained in `sys::get_registry()` until Godot calls `free_property_list()`.
+    ///
+    /// Godot may free the list on another thread than the one requesting it. Thus, the names are copied out of their
+    /// `StringName`/`GodotString` wrappers on the requesting thread, and only FFI data is kept here.
+    struct PropertyListSys {
+        list: Box<[sys::GDNativePropertyInfo]>,
+
+        // Pointed to by `list`; boxed so that the addresses are stable
+        names: Box<[PropertyNamesSys]>,
+    }
+
+    struct PropertyNamesSys {
+        name: sys::types::OpaqueStringName,
+        class_name: sys::types::OpaqueStringName,
+        hint_string: sys::types::OpaqueString,
+    }
+
+    // SAFETY: `list` only points into `names`, which are owned by the same struct and moved with it. Godot's StringName
+    // and String may be destroyed on any thread: StringName::unref() locks a global mutex, and String uses atomic ref-counting.
+    unsafe impl Send for PropertyListSys {}
+
+    impl PropertyListSys {
+        /// Copies the names, so that the list does not depend on the Rust objects anymore.
+        fn new(list: &[sys::GDNativePropertyInfo]) -> Box<Self> {
+            let table = sys::method_table();
+            let names: Box<[PropertyNamesSys]> = list
+                .iter()
+                .map(|info| unsafe {
+                    PropertyNamesSys {
+                        name: copy_sys(info.name, table.string_name_construct_copy),
+                        class_name: copy_sys(info.class_name, table.string_name_construct_copy),
+                        hint_string: copy_sys(info.hint_string, table.string_construct_copy),
+                    }
+                })
+                .collect();
+
+            let mut result = Box::new(Self {
+                list: list.into(),
+                names,
+            });
+
+            let Self { list, names } = &mut *result;
+            for (info, names) in list.iter_mut().zip(names.iter_mut()) {
+                info.name = ptr::addr_of_mut!(names.name) as sys::GDNativeStringNamePtr;
+                info.class_name = ptr::addr_of_mut!(names.class_name) as sys::GDNativeStringNamePtr;
+                info.hint_string = ptr::addr_of_mut!(names.hint_string) as sys::GDNativeStringPtr;
+            }
+
+            result
+        }
+    }
+
+    impl Drop for PropertyListSys {
+        fn drop(&mut self) {
+            let table = sys::method_table();
+            for names in self.names.iter_mut() {
+                let name = ptr::addr_of_mut!(names.name) as sys::GDNativeTypePtr;
+                let class_name = ptr::addr_of_mut!(names.class_name) as sys::GDNativeTypePtr;
+                let hint_string = ptr::addr_of_mut!(names.hint_string) as sys::GDNativeTypePtr;
+
+                unsafe {
+                    (table.string_name_destroy)(name);
+                    (table.string_name_destroy)(class_name);
+                    (table.string_destroy)(hint_string);
+                }
+            }
+        }
+    }
+
+    /// Copy-constructs a builtin into new, owned storage.
+    unsafe fn copy_sys<Opaque>(
+        src: sys::GDNativeTypePtr,
+        copy_ctor: unsafe extern "C" fn(sys::GDNativeTypePtr, *const sys::GDNativeTypePtr),
+    ) -> Opaque {
+        let mut dst = MaybeUninit::<Opaque>::uninit();
+        let args = [src];
+        copy_ctor(dst.as_mut_ptr() as sys::GDNativeTypePtr, args.as_ptr());
+        dst.assume_init()
+    }
+
+    pub unsafe extern "C" fn property_list<T: cap::ImplementsGodotExt>(
+        instance: sys::GDExtensionClassInstancePtr,
+        out_count: *mut u32,
+    ) -> *const sys::GDNativePropertyInfo {
+        *out_count = 0;
+
+        let storage = as_storage::<T>(instance);
+        let instance = match storage.try_get(BindSite::Method("property_list")) {
+            Ok(instance) => instance,
+            Err(conflict) => {
+                godot_error!("{conflict}");
+                return ptr::null();
+            }
+        };
+
+        let list = T::__godot_property_list(&*instance);
+        if list.is_empty() {
+            return ptr::null();
+        }
+
+        // The sys structs point into `list`, which is only dropped after the names have been copied
+        let list_sys: Vec<sys::GDNativePropertyInfo> =
+            list.iter().map(|info| info.property_sys()).collect();
+        let retained = PropertyListSys::new(&list_sys);
+
+        let list_ptr = retained.list.as_ptr();
+        *out_count = u32::try_from(retained.list.len()).expect("too many properties");
+        sys::get_registry().retain(list_ptr, retained);
+
+        list_ptr
+    }
+
+    pub unsafe extern "C" fn free_property_list(
+        _instance: sys::GDExtensionClassInstancePtr,
+        list: *const sys::GDNativePropertyInfo,
+    ) {
+        // Empty lists are returned as null and not retained
+        if !list.is_null() {
+            let released = sys::get_registry().release(list);
+            debug_assert!(released, "property list {list:?} was not retained");
+        }
+    }
+
+    pub unsafe extern "C" fn property_can_revert<T: cap::ImplementsGodotExt>(
+        instance: sys::GDExtensionClassInstancePtr,
+        name: sys::GDNativeStringNamePtr,
+    ) -> sys::GDNativeBool {
+        // Name is owned by Godot and must not be destroyed here
+        let name = ManuallyDrop::new(StringName::from_string_sys(name));
+
+        let storage = as_storage::<T>(instance);
+        let instance = match storage.try_get(BindSite::Method("property_can_revert")) {
+            Ok(instance) => instance,
+            Err(conflict) => {
+                godot_error!("{conflict}");
+                return false as sys::GDNativeBool;
+            }
+        };
+
+        T::__godot_property_can_revert(&*instance, &name) as sys::GDNativeBool
+    }
+
+    pub unsafe extern "C" fn property_get_revert<T: cap::ImplementsGodotExt>(
+        instance: sys::GDExtensionClassInstancePtr,
+        name: sys::GDNativeStringNamePtr,
+        out_value: sys::GDNativeVariantPtr,
+    ) -> sys::GDNativeBool {
+        // Name is owned by Godot and must not be destroyed here
+        let name = ManuallyDrop::new(StringName::from_string_sys(name));
+
+        let storage = as_storage::<T>(instance);
+        let instance = match storage.try_get(BindSite::Method("property_get_revert")) {
+            Ok(instance) => instance,
+            Err(conflict) => {
+                godot_error!("{conflict}");
+                return false as sys::GDNativeBool;
+            }
+        };
+
+        match T::__godot_property_get_revert(&*instance, &name) {
+            Some(value) => {
+                // Transfer ownership to Godot, disable destructor
+                value.write_var_sys(out_value);
+                std::mem::forget(value);
+                true as sys::GDNativeBool
+            }
+            None => false as sys::GDNativeBool,
+        }
+    }
+
     pub unsafe extern "C" fn reference<T: GodotClass>(instance: sys::GDExtensionClassInstancePtr) {
         let storage = as_storage::<T>(instance);
         storage.on_inc_ref();
@@ -349,6 +949,11 @@ pub mod callbacks {
         storage.on_dec_ref();
     }
 
+    /// Classes without `#[class(tool)]` only receive virtual calls and notifications outside the editor.
+    fn is_script_active<T: GodotClass>() -> bool {
+        T::IS_TOOL || !IS_EDITOR_HINT.load(atomic::Ordering::Relaxed)
+    }
+
     // Safe, higher-level methods
 
     /// Abstracts the `GodotInit` away, for contexts where this trait bound is not statically available
@@ -362,35 +967,30 @@ pub mod callbacks {
         Box::new(instance)
     }
 
-    pub fn register_class_by_builder<T: GodotExt>(_class_builder: &mut dyn Any) {
-        // TODO use actual argument, once class builder carries state
-        // let class_builder = class_builder
-        //     .downcast_mut::<ClassBuilder<T>>()
-        //     .expect("bad type erasure");
-
-        let mut class_builder = ClassBuilder::new();
-        T::register_class(&mut class_builder);
+    pub fn register_class_by_builder<T: cap::ImplementsGodotExt>() {
+        let mut class_builder = ClassBuilder::<T>::new();
+        T::__register_class(&mut class_builder);
     }
 
-    pub fn register_user_binds<T: cap::ImplementsGodotApi>(_class_builder: &mut dyn Any) {
-        // let class_builder = class_builder
-        //     .downcast_mut::<ClassBuilder<T>>()
-        //     .expect("bad type erasure");
-
-        //T::register_methods(class_builder);
-        T::__register_methods();
+    pub fn register_user_binds<T: cap::ImplementsGodotApi>() {
+        let mut class_builder = ClassBuilder::<T>::new();
+        T::__register_methods(&mut class_builder);
     }
 }
 
 // Substitute for Default impl
 // Yes, bindgen can implement Default, but only for _all_ types (with single exceptions).
 // For FFI types, it's better to have explicit initialization in the general case though.
-fn default_registration_info(class_name: ClassName) -> ClassRegistrationInfo {
+fn default_registration_info(class_name: &'static str) -> ClassRegistrationInfo {
     ClassRegistrationInfo {
         class_name,
         parent_class_name: None,
-        generated_register_fn: None,
+        definitions: vec![],
+        contributors: vec![],
+        generated_register_fns: vec![],
+        enum_register_fns: vec![],
         user_register_fn: None,
+        is_instantiable: true,
         godot_params: default_creation_info(),
     }
 }
@@ -416,3 +1016,94 @@ fn default_creation_info() -> sys::GDNativeExtensionClassCreationInfo {
         class_userdata: ptr::null_mut(),
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(rust_type: &'static str) -> PluginSource {
+        PluginSource {
+            rust_type,
+            location: "file.rs:1",
+        }
+    }
+
+    fn class(name: &'static str, base: &'static str) -> ClassRegistrationInfo {
+        let mut info = default_registration_info(name);
+        info.parent_class_name = Some(base);
+        info.definitions.push(source(name));
+        info
+    }
+
+    fn validate(
+        classes: impl IntoIterator<Item = ClassRegistrationInfo>,
+    ) -> (Vec<&'static str>, Vec<String>) {
+        let map = classes
+            .into_iter()
+            .map(|info| (info.class_name, info))
+            .collect();
+
+        let (ordered, errors) = validate_classes(map);
+        let names = ordered.iter().map(|info| info.class_name).collect();
+        (names, errors)
+    }
+
+    #[test]
+    fn validate_valid_classes() {
+        let (names, errors) = validate([class("Player", "Node2D"), class("Enemy", "Node2D")]);
+
+        assert_eq!(names, ["Enemy", "Player"]);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn validate_duplicate_definition() {
+        let mut player = class("Player", "Node2D");
+        player.definitions.push(source("other::Player"));
+
+        let (names, errors) = validate([player, class("Enemy", "Node2D")]);
+
+        assert_eq!(names, ["Enemy"]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`Player` is defined multiple times"));
+        assert!(errors[0].contains("`other::Player`"));
+    }
+
+    #[test]
+    fn validate_missing_definition() {
+        let mut player = default_registration_info("Player");
+        player.contributors.push(source("Player"));
+
+        let (names, errors) = validate([player]);
+
+        assert!(names.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`Player` is not defined"));
+    }
+
+    #[test]
+    fn validate_shadowed_engine_class() {
+        // "Node" is used as a base, so it must be the engine class
+        let (names, errors) = validate([
+            class("Node", "Object"),
+            class("Child", "Node"),
+            class("Unrelated", "Node2D"),
+        ]);
+
+        assert_eq!(names, ["Child", "Unrelated"]);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(errors[0].contains("`Node` shadows an engine class"));
+        assert!(errors[0].contains("`Node` (file.rs:1)"));
+    }
+
+    #[test]
+    fn validate_self_base() {
+        let (names, errors) = validate([class("Node", "Node")]);
+
+        assert!(names.is_empty());
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(errors[0].contains("`Node` shadows an engine class"));
+    }
+}
@@ -4,16 +4,26 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use crate::obj::GodotClass;
+use crate::obj::{leaks, GdMut, GdRef, GodotClass, InstanceId, SAFEGUARDS};
 use crate::out;
 use godot_ffi as sys;
 
 use std::any::type_name;
-use std::cell;
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::panic::Location;
+use std::thread::{self, ThreadId};
 
 /// Manages storage and lifecycle of user's extension class instances.
 pub struct InstanceStorage<T: GodotClass> {
-    user_instance: cell::RefCell<T>,
+    user_instance: UnsafeCell<T>,
+    borrow_state: BorrowState,
+
+    // Thread which may bind the user instance: the one it was created on, or the one it was sent to via SendGd
+    owner_thread: Cell<ThreadId>,
+
+    // Token in the leak tracker, if enabled
+    leak_token: Option<u64>,
 
     // Declared after `user_instance`, is dropped last
     pub lifecycle: Lifecycle,
@@ -33,7 +43,10 @@ impl<T: GodotClass> InstanceStorage<T> {
         out!("    Storage::construct             <{}>", type_name::<T>());
 
         Self {
-            user_instance: cell::RefCell::new(user_instance),
+            user_instance: UnsafeCell::new(user_instance),
+            borrow_state: BorrowState::default(),
+            owner_thread: Cell::new(thread::current().id()),
+            leak_token: leaks::on_storage_construct(T::CLASS_NAME),
             lifecycle: Lifecycle::Alive,
             godot_ref_count: 1,
         }
@@ -72,31 +85,78 @@ impl<T: GodotClass> InstanceStorage<T> {
                                    // TODO drop entire Storage
     }*/
 
+    /// Called once the storage is attached to its Godot object.
+    pub(crate) fn on_attached(&self, instance_id: InstanceId) {
+        leaks::on_storage_attached(self.leak_token, instance_id);
+    }
+
     #[must_use]
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
 
-    pub fn get(&self) -> cell::Ref<T> {
-        self.user_instance.try_borrow().unwrap_or_else(|_e| {
-            panic!(
-                "Gd<T>::bind() failed, already bound; T = {}.\n  \
-                 Make sure there is no &mut T live at the time.\n  \
-                 This often occurs when calling a GDScript function/signal from Rust, which then calls again Rust code.",
-                type_name::<T>()
-            )
-        })
+    /// Shared borrow of the user instance, panicking on conflicts.
+    pub fn get(&self, site: BindSite) -> GdRef<T> {
+        self.try_get(site)
+            .unwrap_or_else(|conflict| panic!("{conflict}"))
     }
 
-    pub fn get_mut(&mut self) -> cell::RefMut<T> {
-        self.user_instance.try_borrow_mut().unwrap_or_else(|_e| {
-            panic!(
-                "Gd<T>::bind_mut() failed, already bound; T = {}.\n  \
-                 Make sure there is no &T or &mut T live at the time.\n  \
-                 This often occurs when calling a GDScript function/signal from Rust, which then calls again Rust code.",
-                type_name::<T>()
-            )
-        })
+    /// Exclusive borrow of the user instance, panicking on conflicts.
+    pub fn get_mut(&self, site: BindSite) -> GdMut<T> {
+        self.try_get_mut(site)
+            .unwrap_or_else(|conflict| panic!("{conflict}"))
+    }
+
+    pub(crate) fn try_get(&self, site: BindSite) -> Result<GdRef<T>, BindConflict> {
+        self.check_thread(BindKind::Shared, site)?;
+        let guard = self.borrow_state.acquire::<T>(BindKind::Shared, site)?;
+
+        // SAFETY: borrow state guarantees that no exclusive reference is live
+        let value = unsafe { &*self.user_instance.get() };
+        Ok(GdRef::new(value, guard))
+    }
+
+    pub(crate) fn try_get_mut(&self, site: BindSite) -> Result<GdMut<T>, BindConflict> {
+        self.check_thread(BindKind::Exclusive, site)?;
+        let guard = self.borrow_state.acquire::<T>(BindKind::Exclusive, site)?;
+
+        // SAFETY: borrow state guarantees that no other reference is live
+        let value = unsafe { &mut *self.user_instance.get() };
+        Ok(GdMut::new(value, guard))
+    }
+
+    /// Makes the current thread the owner of the user instance. Used when objects are sent to other threads.
+    pub(crate) fn transfer_to_current_thread(&self) {
+        self.owner_thread.set(thread::current().id());
+    }
+
+    /// Fails if the user instance is accessed from another thread than its owner. Only checked with safeguards.
+    fn check_thread(&self, kind: BindKind, site: BindSite) -> Result<(), BindConflict> {
+        if !SAFEGUARDS {
+            return Ok(());
+        }
+
+        let owner = self.owner_thread.get();
+        let current = thread::current().id();
+        if owner == current {
+            Ok(())
+        } else {
+            Err(BindConflict::WrongThread {
+                class_name: T::CLASS_NAME,
+                requested: kind,
+                requested_site: site,
+                owner,
+                current,
+            })
+        }
+    }
+
+    /// Temporarily releases the exclusive borrow held by the caller, so that re-entrant calls can bind the instance.
+    ///
+    /// The caller must hold the `&mut T` obtained from the exclusive borrow, and not use it until the returned
+    /// suspension is dropped. This is ensured by `base_mut()` borrowing `&mut self`.
+    pub(crate) fn suspend_borrow(&self) -> BorrowSuspension {
+        self.borrow_state.suspend::<T>()
     }
 
     pub fn mark_destroyed_by_godot(&mut self) {
@@ -132,6 +192,7 @@ impl<T: GodotClass> Drop for InstanceStorage<T> {
             //self.user_instance
         );
         //let _ = mem::take(&mut self.user_instance);
+        leaks::on_storage_destroy(self.leak_token);
         out!(
             "    Storage::drop end              <{}>", //  -- {:?}",
             type_name::<T>(),
@@ -182,3 +243,229 @@ extern "C" fn reference_callback(
 ) -> sys::GDNativeBool {
     true as u8
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Borrow tracking
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum BindKind {
+    Shared,
+    Exclusive,
+}
+
+/// Where a borrow of the user instance was requested; used in error messages.
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+pub enum BindSite {
+    /// `Gd::bind()` or `Gd::bind_mut()` in Rust code.
+    Rust(&'static Location<'static>),
+
+    /// Engine calling a registered method (`#[func]` or virtual).
+    Method(&'static str),
+}
+
+impl BindSite {
+    fn describe(&self, kind: BindKind, class_name: &str) -> String {
+        match (self, kind) {
+            (Self::Rust(location), BindKind::Shared) => format!("Gd::bind() at {location}"),
+            (Self::Rust(location), BindKind::Exclusive) => format!("Gd::bind_mut() at {location}"),
+            (Self::Method(method), _) => format!("method {class_name}::{method}()"),
+        }
+    }
+}
+
+/// Outstanding borrow, together with its origin.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct BindHolder {
+    id: u64,
+    kind: BindKind,
+    site: BindSite,
+}
+
+/// Like `RefCell`'s borrow flag, but additionally remembers who holds the borrows.
+#[derive(Default)]
+struct BorrowState {
+    // > 0: number of shared borrows; -1: exclusive borrow; 0: not bound.
+    count: Cell<isize>,
+    holders: RefCell<Vec<BindHolder>>,
+    next_id: Cell<u64>,
+}
+
+impl BorrowState {
+    fn acquire<T: GodotClass>(
+        &self,
+        kind: BindKind,
+        site: BindSite,
+    ) -> Result<BorrowGuard, BindConflict> {
+        let count = self.count.get();
+        let available = match kind {
+            BindKind::Shared => count >= 0,
+            BindKind::Exclusive => count == 0,
+        };
+
+        if !available {
+            return Err(BindConflict::AlreadyBound {
+                class_name: T::CLASS_NAME,
+                requested: kind,
+                requested_site: site,
+                holders: self.holders.borrow().clone(),
+            });
+        }
+
+        self.count.set(match kind {
+            BindKind::Shared => count + 1,
+            BindKind::Exclusive => -1,
+        });
+
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.holders
+            .borrow_mut()
+            .push(BindHolder { id, kind, site });
+
+        Ok(BorrowGuard {
+            state: self as *const _,
+            id,
+        })
+    }
+
+    fn release(&self, id: u64) {
+        let mut holders = self.holders.borrow_mut();
+        if let Some(index) = holders.iter().position(|holder| holder.id == id) {
+            holders.remove(index);
+
+            let count = self.count.get();
+            self.count.set(if count > 0 { count - 1 } else { 0 });
+        }
+        // else: borrow was acquired before a suspension and is released after it; suspension restores it
+    }
+
+    fn suspend<T: GodotClass>(&self) -> BorrowSuspension {
+        BorrowSuspension {
+            state: self as *const _,
+            class_name: T::CLASS_NAME,
+            count: self.count.replace(0),
+            holders: self.holders.take(),
+        }
+    }
+}
+
+/// Releases a borrow of the user instance when dropped.
+///
+/// Holds a raw pointer, since the storage outlives all guards (they are bound to a `Gd` or an engine call).
+pub(crate) struct BorrowGuard {
+    state: *const BorrowState,
+    id: u64,
+}
+
+impl Drop for BorrowGuard {
+    fn drop(&mut self) {
+        // SAFETY: storage outlives the guard
+        unsafe { (*self.state).release(self.id) }
+    }
+}
+
+/// Restores a borrow previously released by [`InstanceStorage::suspend_borrow()`].
+pub(crate) struct BorrowSuspension {
+    state: *const BorrowState,
+    class_name: &'static str,
+    count: isize,
+    holders: Vec<BindHolder>,
+}
+
+impl Drop for BorrowSuspension {
+    fn drop(&mut self) {
+        // SAFETY: storage outlives the suspension, which is bound to a `&mut T` of the user instance
+        let state = unsafe { &*self.state };
+
+        let leftover = state.holders.take();
+        if let Some(holder) = leftover.first() {
+            // Don't panic while already unwinding, that would abort
+            if !std::thread::panicking() {
+                panic!(
+                    "base_mut() guard dropped while a re-entrant borrow is still held by {}",
+                    holder.site.describe(holder.kind, self.class_name)
+                );
+            }
+        }
+
+        state.count.set(self.count);
+        *state.holders.borrow_mut() = std::mem::take(&mut self.holders);
+    }
+}
+
+/// Error when the user instance cannot be bound.
+#[derive(Clone, Debug)]
+pub(crate) enum BindConflict {
+    /// Already bound in an incompatible way.
+    AlreadyBound {
+        class_name: &'static str,
+        requested: BindKind,
+        requested_site: BindSite,
+        holders: Vec<BindHolder>,
+    },
+
+    /// Accessed from another thread than the one owning the instance.
+    WrongThread {
+        class_name: &'static str,
+        requested: BindKind,
+        requested_site: BindSite,
+        owner: ThreadId,
+        current: ThreadId,
+    },
+}
+
+impl Display for BindConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::AlreadyBound {
+                class_name: class,
+                requested,
+                requested_site,
+                holders,
+            } => {
+                let (requested_ref, conflicting) = match requested {
+                    BindKind::Shared => ("&", "&mut"),
+                    BindKind::Exclusive => ("&mut", "& or &mut"),
+                };
+
+                write!(
+                    f,
+                    "{site} failed: cannot borrow `{requested_ref} {class}`, already bound as {conflicting} by:",
+                    site = requested_site.describe(*requested, class),
+                )?;
+
+                for holder in holders.iter() {
+                    let kind = match holder.kind {
+                        BindKind::Shared => "&",
+                        BindKind::Exclusive => "&mut",
+                    };
+                    write!(
+                        f,
+                        "\n  * `{kind} {class}` from {}",
+                        holder.site.describe(holder.kind, class)
+                    )?;
+                }
+
+                write!(
+                    f,
+                    "\n  This often occurs when a method calls into GDScript or emits a signal, which then calls again into the same object.\
+                     \n  Use base_mut() for engine calls from within a bound method; it allows re-entrant access for its duration."
+                )
+            }
+
+            Self::WrongThread {
+                class_name: class,
+                requested,
+                requested_site,
+                owner,
+                current,
+            } => write!(
+                f,
+                "{site} failed: instance of {class} belongs to thread {owner:?}, but was accessed from thread {current:?}.\n  \
+                 Objects may only be used on the thread owning them; use SendGd to hand them over to another thread.",
+                site = requested_site.describe(*requested, class),
+            ),
+        }
+    }
+}
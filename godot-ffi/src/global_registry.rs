@@ -5,8 +5,10 @@
  */
 
 //use crate as sys;
-use std::collections::HashSet;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::ffi::CString;
+use std::sync::Mutex;
 
 // Retains values indefinitely (effectively 'static).
 //
@@ -24,27 +26,50 @@ use std::ffi::CString;
 // buffers (CString::reserve()), UB could be avoided entirely.
 //
 // Reported at https://github.com/godotengine/godot/issues/61968
+//
+// Godot invokes some callbacks from worker threads (e.g. during threaded resource loading), so all state is synchronized.
 #[derive(Default)]
 pub struct GlobalRegistry {
-    c_strings: HashSet<CString>,
+    c_strings: Mutex<HashSet<CString>>,
+
+    // Allocations handed out to Godot, which tells us when it no longer needs them (e.g. property lists).
+    // Keyed by the address of the pointer passed to Godot.
+    retained: Mutex<HashMap<usize, Box<dyn Any + Send>>>,
 }
 
 impl GlobalRegistry {
-    pub fn c_string(&mut self, s: &str) -> *const i8 {
+    pub fn c_string(&self, s: &str) -> *const i8 {
         let value = CString::new(s).expect(&format!("Invalid string '{s}'"));
+        let mut c_strings = self.c_strings.lock().unwrap();
 
-        if let Some(existing) = self.c_strings.get(&value) {
+        // The heap buffer of a CString does not move, so the pointer stays valid after the lock is released
+        if let Some(existing) = c_strings.get(&value) {
             //println!("<<< Cache '{s}'");
             existing.as_ptr()
         } else {
-            //println!(">>> Store '{s}'     [total={}]", self.c_strings.len()+1);
+            //println!(">>> Store '{s}'     [total={}]", c_strings.len()+1);
             let copy = value.clone();
-            self.c_strings.insert(value);
-            let new = self.c_strings.get(&copy).unwrap();
+            c_strings.insert(value);
+            let new = c_strings.get(&copy).unwrap();
             new.as_ptr()
         }
     }
 
+    /// Keeps `value` alive until [`Self::release()`] is called with the same `ptr`.
+    ///
+    /// `ptr` should point into `value`, so that it is unique as long as `value` is alive.
+    pub fn retain<P>(&self, ptr: *const P, value: Box<dyn Any + Send>) {
+        let previous = self.retained.lock().unwrap().insert(ptr as usize, value);
+        assert!(previous.is_none(), "pointer {ptr:?} already retained");
+    }
+
+    /// Drops the value retained for `ptr`. Returns false if there was none.
+    pub fn release<P>(&self, ptr: *const P) -> bool {
+        // Remove first, so that the value is dropped after the lock is released
+        let value = self.retained.lock().unwrap().remove(&(ptr as usize));
+        value.is_some()
+    }
+
     // fn property_info<T>(&mut self, property_name: &str) -> sys::GDNativePropertyInfo {
     //
     // }
@@ -48,7 +48,7 @@ pub use central::*;
 
 /// Late-init globals
 // Note: static mut is _very_ dangerous. Here a bit less so, since modification happens only once (during init) and no
-// &mut references are handed out (except for registry, see below). Overall, UnsafeCell/RefCell + Sync might be a safer abstraction.
+// &mut references are handed out. Overall, UnsafeCell/RefCell + Sync might be a safer abstraction.
 static mut BINDING: Option<GodotBinding> = None;
 
 struct GodotBinding {
@@ -111,11 +111,10 @@ pub unsafe fn method_table() -> &'static GlobalMethodTable {
 ///
 /// The interface must have been initialised with [`initialize`] before calling this function.
 ///
-/// Calling this while another place holds a reference (threads, re-entrancy, iteration, etc) is immediate undefined behavior.
-// note: could potentially avoid &mut aliasing, using UnsafeCell/RefCell
+/// The registry synchronizes access internally, so it may be used from any thread.
 #[inline(always)]
-pub unsafe fn get_registry() -> &'static mut GlobalRegistry {
-    &mut unwrap_ref_unchecked_mut(&mut BINDING).registry
+pub unsafe fn get_registry() -> &'static GlobalRegistry {
+    &unwrap_ref_unchecked(&BINDING).registry
 }
 
 #[macro_export]
@@ -159,14 +158,6 @@ unsafe fn unwrap_ref_unchecked<T>(opt: &Option<T>) -> &T {
     }
 }
 
-unsafe fn unwrap_ref_unchecked_mut<T>(opt: &mut Option<T>) -> &mut T {
-    debug_assert!(opt.is_some(), "unchecked access to Option::None");
-    match opt {
-        Some(ref mut val) => val,
-        None => std::hint::unreachable_unchecked(),
-    }
-}
-
 /// Extract value from box before `into_inner()` is stable
 pub fn unbox<T>(value: Box<T>) -> T {
     // Deref-move is a Box magic feature; see https://stackoverflow.com/a/42264074
@@ -6,9 +6,9 @@
 
 use crate::util::{bail, ensure_kv_empty, ident, path_is_single, KvMap, KvValue};
 use crate::{util, ParseResult};
-use proc_macro2::{Ident, Punct, Span, TokenStream};
+use proc_macro2::{Ident, Literal, Punct, Span, TokenStream, TokenTree};
 use quote::spanned::Spanned;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use venial::{Attribute, NamedField, Struct, StructFields, TyExpr};
 
 pub fn transform(input: TokenStream) -> ParseResult<TokenStream> {
@@ -24,43 +24,114 @@ pub fn transform(input: TokenStream) -> ParseResult<TokenStream> {
     let base_ty = &struct_cfg.base_ty;
     let base_ty_str = struct_cfg.base_ty.to_string();
     let class_name = &class.name;
-    let class_name_str = class.name.to_string();
+    let is_tool = struct_cfg.is_tool;
+    let is_abstract = struct_cfg.is_abstract;
+    let is_hidden = struct_cfg.is_hidden;
+    let is_instantiable = !struct_cfg.is_abstract && !struct_cfg.is_no_init;
     let inherits_macro = format_ident!("inherits_transitive_{}", &base_ty_str);
 
     let prv = quote! { ::godot::private };
-    let deref_impl = make_deref_impl(class_name, &fields);
+    let target = ImplTarget::new(class);
+    let ImplTarget {
+        params,
+        self_ty,
+        where_clause,
+    } = &target;
+
+    let deref_impl = make_deref_impl(&target, &fields);
+    let with_base_impl = make_with_base_impl(&target, &fields);
+    let (resolve_on_ready_impl, notification_fn) = make_resolve_on_ready_impl(&target, &fields);
 
     let (godot_init_impl, create_fn);
+    if !struct_cfg.has_generated_init {
+        if let Some(field) = fields
+            .all_fields
+            .iter()
+            .find(|field| !matches!(field.init, FieldInit::Default))
+        {
+            bail(
+                "#[init] on fields requires #[class(init)], to generate the init function",
+                &field.name,
+            )?;
+        }
+    }
+
     if struct_cfg.has_generated_init {
-        godot_init_impl = make_godot_init_impl(class_name, fields);
-        create_fn = quote! { Some(#prv::callbacks::create::<#class_name>) };
+        godot_init_impl = make_godot_init_impl(&target, fields);
+        create_fn = quote! { Some(#prv::callbacks::create::<#self_ty>) };
     } else {
         godot_init_impl = TokenStream::new();
         create_fn = quote! { None };
     };
 
+    let class_def = quote! {
+        #prv::PluginComponent::ClassDef {
+            base_class_name: <::godot::engine::#base_ty as ::godot::obj::GodotClass>::CLASS_NAME,
+            generated_create_fn: #create_fn,
+            free_fn: #prv::callbacks::free::<#self_ty>,
+            generated_on_notification_fn: #notification_fn,
+            is_abstract: #is_abstract,
+            is_hidden: #is_hidden,
+            is_instantiable: #is_instantiable,
+        }
+    };
+
+    // Generic classes are registered once per #[godot_alias], which also provides the class name
+    let (class_name_str, registration);
+    if target.is_generic() {
+        if struct_cfg.rename.is_some() {
+            bail(
+                "Generic classes cannot be renamed; use #[godot_alias(rename = \"...\")] on each instantiation instead",
+                &class.name,
+            )?;
+        }
+
+        let user_where_clause = &class.where_clause;
+        class_name_str = quote! { <Self as ::godot::obj::cap::ClassAlias>::CLASS_NAME };
+        registration = quote! {
+            impl #params ::godot::obj::cap::GenericClass for #self_ty #where_clause {
+                fn __class_def() -> #prv::PluginComponent {
+                    #class_def
+                }
+            }
+
+            #prv::class_macros::#inherits_macro!(#self_ty, [#params] [#user_where_clause]);
+        };
+    } else {
+        let name_str = struct_cfg
+            .rename
+            .clone()
+            .unwrap_or_else(|| class_name.to_string());
+
+        let source = util::plugin_source(class_name);
+        class_name_str = quote! { #name_str };
+        registration = quote! {
+            ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
+                class_name: #name_str,
+                #source
+                component: #class_def,
+            });
+
+            #prv::class_macros::#inherits_macro!(#class_name);
+        };
+    }
+
     Ok(quote! {
-        impl ::godot::obj::GodotClass for #class_name {
+        impl #params ::godot::obj::GodotClass for #self_ty #where_clause {
             type Base = ::godot::engine::#base_ty;
             type Declarer = ::godot::obj::dom::UserDomain;
             type Mem = <Self::Base as ::godot::obj::GodotClass>::Mem;
 
             const CLASS_NAME: &'static str = #class_name_str;
+            const IS_TOOL: bool = #is_tool;
+
+            #resolve_on_ready_impl
         }
 
         #godot_init_impl
         #deref_impl
-
-        ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
-            class_name: #class_name_str,
-            component: #prv::PluginComponent::ClassDef {
-                base_class_name: #base_ty_str,
-                generated_create_fn: #create_fn,
-                free_fn: #prv::callbacks::free::<#class_name>,
-            },
-        });
-
-        #prv::class_macros::#inherits_macro!(#class_name);
+        #with_base_impl
+        #registration
     })
 }
 
@@ -69,6 +140,11 @@ fn parse_struct_attributes(class: &Struct) -> ParseResult<ClassAttributes> {
     let mut base = ident("RefCounted");
     //let mut new_mode = GodotConstructMode::AutoGenerated;
     let mut has_generated_init = false;
+    let mut rename = None;
+    let mut is_tool = false;
+    let mut is_abstract = false;
+    let mut is_hidden = false;
+    let mut is_no_init = false;
 
     // #[func] attribute on struct
     if let Some((span, mut map)) = parse_class_attr(&class.attributes)? {
@@ -98,18 +174,55 @@ fn parse_struct_attributes(class: &Struct) -> ParseResult<ClassAttributes> {
                 _ => bail("Argument 'init' must not have a value", span)?,
             }
         }
+
+        // #[class(rename = "Name")]
+        if let Some(kv_value) = map.remove("rename") {
+            match kv_value {
+                KvValue::Lit(lit)
+                    if lit.len() > 2 && lit.starts_with('"') && lit.ends_with('"') =>
+                {
+                    rename = Some(lit[1..lit.len() - 1].to_string());
+                }
+                _ => bail("Argument 'rename' must be a non-empty string literal", span)?,
+            }
+        }
+
+        is_tool = parse_flag(&mut map, "tool", span)?;
+        is_abstract = parse_flag(&mut map, "abstract", span)?;
+        is_hidden = parse_flag(&mut map, "hidden", span)?;
+        is_no_init = parse_flag(&mut map, "no_init", span)?;
+        if is_no_init && has_generated_init {
+            bail(
+                "Arguments 'init' and 'no_init' are mutually exclusive",
+                span,
+            )?;
+        }
         ensure_kv_empty(map, span)?;
     }
 
     Ok(ClassAttributes {
         base_ty: base,
         has_generated_init,
+        rename,
+        is_tool,
+        is_abstract,
+        is_hidden,
+        is_no_init,
     })
 }
 
+/// Removes a key without value like `tool` from the map, returning whether it was present
+fn parse_flag(map: &mut KvMap, key: &str, span: Span) -> ParseResult<bool> {
+    match map.remove(key) {
+        None => Ok(false),
+        Some(KvValue::None) => Ok(true),
+        Some(_) => bail(format!("Argument '{key}' must not have a value"), span),
+    }
+}
+
 /// Returns field names and 1 base field, if available
 fn parse_fields(class: &Struct) -> ParseResult<Fields> {
-    let mut all_field_names = vec![];
+    let mut all_fields = vec![];
     let mut exported_fields = vec![];
     let mut base_field = Option::<ExportedField>::None;
 
@@ -127,11 +240,17 @@ fn parse_fields(class: &Struct) -> ParseResult<Fields> {
     // Attributes on struct fields
     for (field, _punct) in fields {
         let mut is_base = false;
+        let mut init = None;
 
-        // #[base] or #[export]
+        // #[base], #[export] or #[init]
         for attr in field.attributes.iter() {
             if let Some(path) = attr.get_single_path_segment() {
-                if path == "base" {
+                if path == "init" {
+                    if init.is_some() {
+                        bail("Only one #[init] attribute per field allowed", attr)?;
+                    }
+                    init = Some(parse_field_init(attr)?);
+                } else if path == "base" {
                     is_base = true;
                     if let Some(prev_base) = base_field {
                         bail(
@@ -150,17 +269,60 @@ fn parse_fields(class: &Struct) -> ParseResult<Fields> {
         }
 
         // Exported or Rust-only fields
-        if !is_base {
-            all_field_names.push(field.name.clone())
+        if is_base {
+            if init.is_some() {
+                bail(
+                    "#[init] cannot be applied to the #[base] field",
+                    &field.name,
+                )?;
+            }
+        } else {
+            all_fields.push(InitField {
+                name: field.name.clone(),
+                init: init.unwrap_or(FieldInit::Default),
+            })
         }
     }
 
     Ok(Fields {
-        all_field_names,
+        all_fields,
         base_field,
     })
 }
 
+/// Parses `#[init(default = expr)]` or `#[init(node = "Path")]`.
+///
+/// Not handled by `parse_kv_group()`, since the default value can be an arbitrary expression.
+fn parse_field_init(attr: &Attribute) -> ParseResult<FieldInit> {
+    let tokens: &[TokenTree] = attr.value.get_value_tokens();
+
+    let (key, value) = match tokens {
+        [TokenTree::Ident(key), TokenTree::Punct(eq), value @ ..]
+            if eq.as_char() == '=' && !value.is_empty() =>
+        {
+            (key.to_string(), value)
+        }
+        _ => bail(
+            "#[init] expects either `default = expr` or `node = \"Path\"`",
+            attr,
+        )?,
+    };
+
+    match key.as_str() {
+        "default" => Ok(FieldInit::Expr(value.iter().cloned().collect())),
+        "node" => match value {
+            [TokenTree::Literal(path)] if path.to_string().starts_with('"') => {
+                Ok(FieldInit::Node(path.clone()))
+            }
+            _ => bail(
+                "#[init(node)] expects a string literal with the node path",
+                attr,
+            ),
+        },
+        _ => bail(format!("#[init]: unknown key '{key}'"), attr),
+    }
+}
+
 /// Parses a `#[class(...)]` attribute
 fn parse_class_attr(attributes: &Vec<Attribute>) -> ParseResult<Option<(Span, KvMap)>> {
     let mut godot_attr = None;
@@ -184,16 +346,79 @@ fn parse_class_attr(attributes: &Vec<Attribute>) -> ParseResult<Option<(Span, Kv
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // General helpers
 
+/// Generic parameters, `Self` type and where-clause of the impls generated for a class
+struct ImplTarget {
+    params: TokenStream,
+    self_ty: TokenStream,
+    where_clause: TokenStream,
+}
+
+impl ImplTarget {
+    fn new(class: &Struct) -> Self {
+        let class_name = &class.name;
+
+        match &class.generic_params {
+            None => Self {
+                params: TokenStream::new(),
+                self_ty: quote! { #class_name },
+                where_clause: TokenStream::new(),
+            },
+            Some(params) => {
+                let args = class.get_inline_generic_args();
+                let self_ty = quote! { #class_name #args };
+
+                // GodotClass is only implemented for instantiations declared with #[godot_alias]
+                let where_clause = util::prepend_where_bound(
+                    &class.where_clause,
+                    quote! { #self_ty: ::godot::obj::cap::ClassAlias },
+                );
+
+                Self {
+                    params: quote! { #params },
+                    self_ty,
+                    where_clause,
+                }
+            }
+        }
+    }
+
+    fn is_generic(&self) -> bool {
+        !self.params.is_empty()
+    }
+}
+
 struct ClassAttributes {
     base_ty: Ident,
     has_generated_init: bool,
+    rename: Option<String>,
+    is_tool: bool,
+    is_abstract: bool,
+    is_hidden: bool,
+    is_no_init: bool,
 }
 
 struct Fields {
-    all_field_names: Vec<Ident>,
+    all_fields: Vec<InitField>,
     base_field: Option<ExportedField>,
 }
 
+/// Non-base field, with its initializer in the generated `init`
+struct InitField {
+    name: Ident,
+    init: FieldInit,
+}
+
+enum FieldInit {
+    /// No `#[init]` attribute: `Default::default()`
+    Default,
+
+    /// `#[init(default = expr)]`
+    Expr(TokenStream),
+
+    /// `#[init(node = "Path")]`: an `OnReady` which looks up the child node once the class is ready
+    Node(Literal),
+}
+
 struct ExportedField {
     name: Ident,
     _ty: TyExpr,
@@ -208,19 +433,38 @@ impl ExportedField {
     }
 }
 
-fn make_godot_init_impl(class_name: &Ident, fields: Fields) -> TokenStream {
+fn make_godot_init_impl(target: &ImplTarget, fields: Fields) -> TokenStream {
+    let ImplTarget {
+        params,
+        self_ty,
+        where_clause,
+    } = target;
+
     let base_init = if let Some(ExportedField { name, .. }) = fields.base_field {
         quote! { #name: base, }
     } else {
         TokenStream::new()
     };
 
-    let rest_init = fields.all_field_names.into_iter().map(|field| {
-        quote! { #field: std::default::Default::default(), }
+    // Spanned to the field, so that a missing Default impl or a type mismatch is reported there
+    let rest_init = fields.all_fields.into_iter().map(|field| {
+        let InitField { name, init } = field;
+        match init {
+            FieldInit::Default => quote_spanned! { name.span()=>
+                #name: ::std::default::Default::default(),
+            },
+            FieldInit::Expr(expr) => quote_spanned! { name.span()=>
+                #name: #expr,
+            },
+            FieldInit::Node(path) => quote_spanned! { name.span()=>
+                #name: ::godot::obj::OnReady::node(&base, #path),
+            },
+        }
     });
 
+    // Base is moved last, as node initializers need it
     quote! {
-        impl ::godot::obj::cap::GodotInit for #class_name {
+        impl #params ::godot::obj::cap::GodotInit for #self_ty #where_clause {
             fn __godot_init(base: ::godot::obj::Base<Self::Base>) -> Self {
                 Self {
                     #( #rest_init )*
@@ -231,25 +475,78 @@ fn make_godot_init_impl(class_name: &Ident, fields: Fields) -> TokenStream {
     }
 }
 
-fn make_deref_impl(class_name: &Ident, fields: &Fields) -> TokenStream {
+/// Returns the `__godot_resolve_on_ready()` override and the notification callback, if there are `#[init(node)]` fields
+fn make_resolve_on_ready_impl(target: &ImplTarget, fields: &Fields) -> (TokenStream, TokenStream) {
+    let node_fields: Vec<&Ident> = fields
+        .all_fields
+        .iter()
+        .filter(|field| matches!(field.init, FieldInit::Node(_)))
+        .map(|field| &field.name)
+        .collect();
+
+    if node_fields.is_empty() {
+        return (TokenStream::new(), quote! { None });
+    }
+
+    let self_ty = &target.self_ty;
+    let resolve_impl = quote! {
+        fn __godot_resolve_on_ready(&self) {
+            #( self.#node_fields.__resolve_on_ready(); )*
+        }
+    };
+    let notification_fn =
+        quote! { Some(::godot::private::callbacks::on_ready_notification::<#self_ty>) };
+
+    (resolve_impl, notification_fn)
+}
+
+fn make_deref_impl(target: &ImplTarget, fields: &Fields) -> TokenStream {
     let base_field = if let Some(ExportedField { name, .. }) = &fields.base_field {
         name
     } else {
         return TokenStream::new();
     };
 
+    let ImplTarget {
+        params,
+        self_ty,
+        where_clause,
+    } = target;
+
     quote! {
-        impl std::ops::Deref for #class_name {
+        impl #params std::ops::Deref for #self_ty #where_clause {
             type Target = <Self as ::godot::obj::GodotClass>::Base;
 
             fn deref(&self) -> &Self::Target {
                 &*self.#base_field
             }
         }
-        impl std::ops::DerefMut for #class_name {
+        impl #params std::ops::DerefMut for #self_ty #where_clause {
             fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut *self.#base_field
             }
         }
     }
 }
+
+fn make_with_base_impl(target: &ImplTarget, fields: &Fields) -> TokenStream {
+    let base_field = if let Some(ExportedField { name, .. }) = &fields.base_field {
+        name
+    } else {
+        return TokenStream::new();
+    };
+
+    let ImplTarget {
+        params,
+        self_ty,
+        where_clause,
+    } = target;
+
+    quote! {
+        impl #params ::godot::obj::WithBaseField for #self_ty #where_clause {
+            fn __base(&self) -> &::godot::obj::Base<<Self as ::godot::obj::GodotClass>::Base> {
+                &self.#base_field
+            }
+        }
+    }
+}
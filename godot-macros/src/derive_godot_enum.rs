@@ -0,0 +1,225 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::util::{bail, ensure_kv_empty, path_is_single, KvMap, KvValue};
+use crate::{util, ParseResult};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use quote::spanned::Spanned;
+use venial::{Attribute, Enum, StructFields};
+
+pub fn transform(input: TokenStream) -> ParseResult<TokenStream> {
+    let decl = venial::parse_declaration(input)?;
+
+    let enum_ = decl.as_enum().ok_or(venial::Error::new(
+        "#[derive(GodotEnum)] only works on enums",
+    ))?;
+
+    if enum_.generic_params.is_some() {
+        bail(
+            "#[derive(GodotEnum)] does not support generic parameters",
+            enum_,
+        )?;
+    }
+
+    let enum_cfg = parse_enum_attributes(enum_)?;
+    let enumerators = parse_enumerators(enum_)?;
+
+    let enum_name = &enum_.name;
+    let enum_name_str = enum_name.to_string();
+    let enumerator_name_strs: Vec<String> = enumerators.iter().map(|e| e.to_string()).collect();
+
+    let prv = quote! { ::godot::private };
+    let is_bitfield = enum_cfg.is_bitfield;
+    let hint = if is_bitfield {
+        quote! { PROPERTY_HINT_FLAGS }
+    } else {
+        quote! { PROPERTY_HINT_ENUM }
+    };
+
+    let register_plugin = if let Some(class_name) = &enum_cfg.class_name {
+        let class_name_str = quote! { <#class_name as ::godot::obj::GodotClass>::CLASS_NAME };
+        let source = util::plugin_source(class_name);
+        quote! {
+            ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
+                class_name: #class_name_str,
+                #source
+                component: #prv::PluginComponent::UserEnum {
+                    generated_register_fn: #prv::ErasedRegisterFn {
+                        raw: {
+                            fn register() {
+                                let class_name = ::godot::builtin::meta::ClassName::new::<#class_name>();
+                                #(
+                                    #prv::register_integer_constant(
+                                        &class_name,
+                                        #enum_name_str,
+                                        #enumerator_name_strs,
+                                        #enum_name::#enumerators as i64,
+                                        #is_bitfield,
+                                    );
+                                )*
+                            }
+                            register
+                        },
+                    },
+                },
+            });
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    Ok(quote! {
+        impl ::godot::obj::EngineEnum for #enum_name {
+            fn try_from_ord(ord: i32) -> Option<Self> {
+                #(
+                    if ord == #enum_name::#enumerators as i32 {
+                        return Some(#enum_name::#enumerators);
+                    }
+                )*
+                None
+            }
+
+            fn ord(self) -> i32 {
+                self as i32
+            }
+        }
+
+        impl ::godot::builtin::ToVariant for #enum_name {
+            fn to_variant(&self) -> ::godot::builtin::Variant {
+                let ord = <Self as ::godot::obj::EngineEnum>::ord(*self);
+                <i64 as ::godot::builtin::ToVariant>::to_variant(&i64::from(ord))
+            }
+        }
+
+        impl ::godot::builtin::FromVariant for #enum_name {
+            fn try_from_variant(
+                variant: &::godot::builtin::Variant,
+            ) -> Result<Self, ::godot::builtin::VariantConversionError> {
+                let ord = <i64 as ::godot::builtin::FromVariant>::try_from_variant(variant)?;
+                i32::try_from(ord)
+                    .ok()
+                    .and_then(<Self as ::godot::obj::EngineEnum>::try_from_ord)
+                    .ok_or(::godot::builtin::VariantConversionError)
+            }
+        }
+
+        impl ::godot::builtin::meta::VariantMetadata for #enum_name {
+            fn variant_type() -> ::godot::builtin::VariantType {
+                ::godot::builtin::VariantType::Int
+            }
+
+            fn property_info(property_name: &str) -> ::godot::builtin::meta::PropertyInfo {
+                let hint_string = [
+                    #( format!("{}:{}", #enumerator_name_strs, #enum_name::#enumerators as i64) ),*
+                ].join(",");
+
+                ::godot::builtin::meta::PropertyInfo::new(
+                    ::godot::builtin::VariantType::Int,
+                    ::godot::builtin::meta::ClassName::new::<()>(),
+                    ::godot::builtin::StringName::from(property_name),
+                )
+                .with_hint(::godot::engine::global::PropertyHint::#hint, hint_string.as_str())
+            }
+        }
+
+        impl ::godot::sys::GodotFuncMarshal for #enum_name {
+            type Via = i64;
+
+            unsafe fn try_from_sys(ptr: ::godot::sys::GDNativeTypePtr) -> Result<Self, i64> {
+                let via = <i64 as ::godot::sys::GodotFfi>::from_sys(ptr);
+                i32::try_from(via)
+                    .ok()
+                    .and_then(<Self as ::godot::obj::EngineEnum>::try_from_ord)
+                    .ok_or(via)
+            }
+
+            unsafe fn try_write_sys(&self, dst: ::godot::sys::GDNativeTypePtr) -> Result<(), Self> {
+                let via = i64::from(<Self as ::godot::obj::EngineEnum>::ord(*self));
+                <i64 as ::godot::sys::GodotFfi>::write_sys(&via, dst);
+                Ok(())
+            }
+        }
+
+        #register_plugin
+    })
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// General helpers
+
+struct EnumAttributes {
+    class_name: Option<Ident>,
+    is_bitfield: bool,
+}
+
+/// Parses the optional `#[godot_enum(class = MyClass, bitfield)]` attribute
+fn parse_enum_attributes(enum_: &Enum) -> ParseResult<EnumAttributes> {
+    let mut class_name = None;
+    let mut is_bitfield = false;
+
+    if let Some((span, mut map)) = parse_godot_enum_attr(&enum_.attributes)? {
+        if let Some(kv_value) = map.remove("class") {
+            if let KvValue::Ident(ident) = kv_value {
+                class_name = Some(ident);
+            } else {
+                bail("Invalid value for 'class' argument", span)?;
+            }
+        }
+
+        if let Some(kv_value) = map.remove("bitfield") {
+            match kv_value {
+                KvValue::None => is_bitfield = true,
+                _ => bail("Argument 'bitfield' must not have a value", span)?,
+            }
+        }
+        ensure_kv_empty(map, span)?;
+    }
+
+    Ok(EnumAttributes {
+        class_name,
+        is_bitfield,
+    })
+}
+
+fn parse_godot_enum_attr(attributes: &[Attribute]) -> ParseResult<Option<(Span, KvMap)>> {
+    let mut godot_attr = None;
+    for attr in attributes.iter() {
+        if path_is_single(&attr.path, "godot_enum") {
+            if godot_attr.is_some() {
+                bail("Only one #[godot_enum] attribute per enum allowed", attr)?;
+            }
+
+            let map = util::parse_kv_group(&attr.value)?;
+            godot_attr = Some((attr.__span(), map));
+        }
+    }
+    Ok(godot_attr)
+}
+
+/// Returns the enumerator names; all of them must be fieldless
+fn parse_enumerators(enum_: &Enum) -> ParseResult<Vec<Ident>> {
+    let mut enumerators = vec![];
+    for (variant, _punct) in enum_.variants.inner.iter() {
+        if !matches!(variant.contents, StructFields::Unit) {
+            bail(
+                "#[derive(GodotEnum)] only supports enumerators without fields",
+                &variant.name,
+            )?;
+        }
+
+        enumerators.push(variant.name.clone());
+    }
+
+    if enumerators.is_empty() {
+        bail(
+            "#[derive(GodotEnum)] requires at least one enumerator",
+            enum_,
+        )?;
+    }
+
+    Ok(enumerators)
+}
@@ -0,0 +1,111 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::util::bail;
+use crate::{util, ParseResult};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::quote;
+
+pub fn transform(meta: TokenStream, input: TokenStream) -> ParseResult<TokenStream> {
+    let alias_name = parse_alias_name(&input)?;
+    let class_name_str = parse_rename(meta)?.unwrap_or_else(|| alias_name.to_string());
+
+    let prv = quote! { ::godot::private };
+    let class_name = quote! { <#alias_name as ::godot::obj::GodotClass>::CLASS_NAME };
+    let source = util::plugin_source(&alias_name);
+
+    Ok(quote! {
+        #input
+
+        impl ::godot::obj::cap::ClassAlias for #alias_name {
+            const CLASS_NAME: &'static str = #class_name_str;
+        }
+
+        ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
+            class_name: #class_name,
+            #source
+            component: <#alias_name as ::godot::obj::cap::GenericClass>::__class_def(),
+        });
+
+        // Generic #[godot_api] blocks are optional, see generic_probe
+        ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
+            class_name: #class_name,
+            #source
+            component: {
+                use #prv::generic_probe::{GenericProbe, ProbeMethodBinds as _, ProbeMethodBindsFallback as _};
+                (&GenericProbe::<#alias_name>(::std::marker::PhantomData)).__method_binds()
+            },
+        });
+
+        ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
+            class_name: #class_name,
+            #source
+            component: {
+                use #prv::generic_probe::{GenericProbe, ProbeVirtuals as _, ProbeVirtualsFallback as _};
+                (&GenericProbe::<#alias_name>(::std::marker::PhantomData)).__virtuals()
+            },
+        });
+    })
+}
+
+/// Returns `Name` in `[attributes] [pub] type Name = Type;`
+///
+/// Parsed by hand; the alias itself is emitted unchanged.
+fn parse_alias_name(input: &TokenStream) -> ParseResult<Ident> {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let mut rest = tokens.as_slice();
+
+    // Attributes and visibility, e.g. `#[doc = "..."]` or `pub(crate)`
+    loop {
+        match rest {
+            [TokenTree::Punct(hash), TokenTree::Group(_), tail @ ..] if hash.as_char() == '#' => {
+                rest = tail
+            }
+            [TokenTree::Ident(vis), TokenTree::Group(_), tail @ ..] if vis == "pub" => rest = tail,
+            [TokenTree::Ident(vis), tail @ ..] if vis == "pub" => rest = tail,
+            _ => break,
+        }
+    }
+
+    match rest {
+        [TokenTree::Ident(tk_type), TokenTree::Ident(name), TokenTree::Punct(eq), ..]
+            if tk_type == "type" && eq.as_char() == '=' =>
+        {
+            Ok(name.clone())
+        }
+        [TokenTree::Ident(tk_type), TokenTree::Ident(name), ..] if tk_type == "type" => bail(
+            "#[godot_alias] requires a concrete type, without generic parameters",
+            name,
+        ),
+        _ => bail(
+            "#[godot_alias] can only be applied to type aliases, e.g. `type EnemyPool = Pool<Enemy>;`",
+            input,
+        ),
+    }
+}
+
+/// Parses the optional `rename = "Name"` argument
+fn parse_rename(meta: TokenStream) -> ParseResult<Option<String>> {
+    let tokens: Vec<TokenTree> = meta.clone().into_iter().collect();
+
+    match tokens.as_slice() {
+        [] => Ok(None),
+        [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(lit)]
+            if key == "rename" && eq.as_char() == '=' =>
+        {
+            let lit_str = lit.to_string();
+            if lit_str.len() > 2 && lit_str.starts_with('"') && lit_str.ends_with('"') {
+                Ok(Some(lit_str[1..lit_str.len() - 1].to_string()))
+            } else {
+                bail("Argument 'rename' must be a non-empty string literal", lit)
+            }
+        }
+        _ => bail(
+            "#[godot_alias] expects no arguments or `rename = \"Name\"`",
+            meta,
+        ),
+    }
+}
@@ -7,11 +7,8 @@
 use crate::util;
 use crate::util::bail;
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
-use venial::{AttributeValue, Declaration, Error, Function, Impl, ImplMember};
-
-// Note: keep in sync with trait GodotExt
-const VIRTUAL_METHOD_NAMES: [&'static str; 3] = ["ready", "process", "physics_process"];
+use quote::{quote, ToTokens};
+use venial::{AttributeValue, Declaration, Error, FnParam, Function, Impl, ImplMember};
 
 pub fn transform(input: TokenStream) -> Result<TokenStream, Error> {
     let input_decl = venial::parse_declaration(input)?;
@@ -23,19 +20,14 @@ pub fn transform(input: TokenStream) -> Result<TokenStream, Error> {
         )?,
     };
 
-    if decl.impl_generic_params.is_some() {
-        bail(
-            "#[godot_api] currently does not support generic parameters",
-            &decl,
-        )?;
-    }
-
     if decl.self_ty.as_path().is_none() {
         return bail("invalid Self type for #[godot_api] impl", decl);
     };
 
     if decl.trait_ty.is_some() {
         transform_trait_impl(decl)
+    } else if decl.impl_generic_params.is_some() {
+        transform_generic_inherent_impl(decl)
     } else {
         transform_inherent_impl(decl)
     }
@@ -61,51 +53,62 @@ impl BoundAttr {
     }
 }
 
-/// Codegen for `#[godot_api] impl MyType`
+/// Codegen for `#[godot_api] impl MyType`, or `impl MyType<Concrete>` for an instantiation of a generic class
 fn transform_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
-    let class_name = util::validate_impl(&decl, None, "godot_api")?;
-    let class_name_str = class_name.to_string();
-
-    //let register_fn = format_ident!("__godot_rust_register_{}", class_name_str);
-    //#[allow(non_snake_case)]
+    // Not an ident, since a concrete instantiation of a generic class has type arguments
+    let class_ty = decl.self_ty.clone();
+    let class_name_str = quote! { <#class_ty as ::godot::obj::GodotClass>::CLASS_NAME };
+    let source = util::plugin_source(&class_ty);
 
     let (funcs, signals) = process_godot_fns(&mut decl)?;
     let signal_name_strs = signals.into_iter().map(|ident| ident.to_string());
+    let constants = process_godot_constants(&mut decl)?;
+    let constant_name_strs = constants.iter().map(|ident| ident.to_string());
 
     let prv = quote! { ::godot::private };
 
+    // No trait impl, but a free function per block: a class may have any number of #[godot_api] impl blocks
     let result = quote! {
         #decl
 
-        impl ::godot::obj::cap::ImplementsGodotApi for #class_name {
-            //fn __register_methods(_builder: &mut ::godot::builder::ClassBuilder<Self>) {
-            fn __register_methods() {
-                #(
-                    ::godot::private::gdext_register_method!(#class_name, #funcs);
-                )*
-
-                unsafe {
-                    let class_name = ::godot::builtin::StringName::from(#class_name_str);
-                    use ::godot::sys;
-                    #(
-                        let signal_name = ::godot::builtin::StringName::from(#signal_name_strs);
-                        sys::interface_fn!(classdb_register_extension_class_signal)(
-                            sys::get_library(),
-                            class_name.string_sys(),
-                            signal_name.string_sys(),
-                            std::ptr::null(), // NULL only valid for zero parameters, in current impl; maybe better empty slice
-                            0,
-                        );
-                    )*
-                }
-            }
-        }
-
         ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
             class_name: #class_name_str,
+            #source
             component: #prv::PluginComponent::UserMethodBinds {
                 generated_register_fn: #prv::ErasedRegisterFn {
-                    raw: #prv::callbacks::register_user_binds::<#class_name>,
+                    raw: {
+                        fn __register_methods() {
+                            #(
+                                ::godot::private::gdext_register_method!(#class_ty, #funcs);
+                            )*
+
+                            unsafe {
+                                let class_name = ::godot::builtin::StringName::from(#class_name_str);
+                                use ::godot::sys;
+                                #(
+                                    let signal_name = ::godot::builtin::StringName::from(#signal_name_strs);
+                                    sys::interface_fn!(classdb_register_extension_class_signal)(
+                                        sys::get_library(),
+                                        class_name.string_sys(),
+                                        signal_name.string_sys(),
+                                        std::ptr::null(), // NULL only valid for zero parameters, in current impl; maybe better empty slice
+                                        0,
+                                    );
+                                )*
+                            }
+
+                            #(
+                                #prv::register_integer_constant(
+                                    &::godot::builtin::meta::ClassName::new::<#class_ty>(),
+                                    "",
+                                    #constant_name_strs,
+                                    <#class_ty>::#constants as i64,
+                                    false,
+                                );
+                            )*
+                        }
+                        __register_methods
+                    },
                 },
             },
         });
@@ -114,6 +117,99 @@ fn transform_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
     Ok(result)
 }
 
+/// Codegen for `#[godot_api] impl<T> MyType<T>`
+///
+/// The class is registered once per `#[godot_alias]` instantiation, so this generates a trait impl instead of a plugin.
+/// Since `gdext_register_method!` cannot refer to generic parameters, methods are registered through the `ClassBuilder`.
+///
+/// Only one generic block per class is possible, as each implements `ImplementsGodotApi`. Further blocks must be
+/// concrete (`impl MyType<Concrete>`), which can exist any number of times.
+fn transform_generic_inherent_impl(mut decl: Impl) -> Result<TokenStream, Error> {
+    let class_ty = decl.self_ty.clone();
+    let impl_params = decl.impl_generic_params.clone();
+    let user_where_clause = decl.where_clause.clone();
+    let where_clause = util::prepend_where_bound(
+        &decl.where_clause,
+        quote! { #class_ty: ::godot::obj::GodotClass },
+    );
+
+    let (funcs, signals) = process_godot_fns(&mut decl)?;
+    let method_registrations = funcs
+        .iter()
+        .map(make_builder_method)
+        .collect::<Result<Vec<_>, _>>()?;
+    let signal_name_strs = signals.into_iter().map(|ident| ident.to_string());
+    let constants = process_godot_constants(&mut decl)?;
+    let constant_name_strs = constants.iter().map(|ident| ident.to_string());
+
+    // A second generic block would only report conflicting ImplementsGodotApi impls; the duplicate const names the cause
+    let result = quote! {
+        #decl
+
+        impl #impl_params #class_ty #user_where_clause {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            const __ONLY_ONE_GENERIC_GODOT_API_BLOCK_PER_CLASS_USE_CONCRETE_BLOCKS_FOR_MORE: () = ();
+        }
+
+        impl #impl_params ::godot::obj::cap::ImplementsGodotApi for #class_ty #where_clause {
+            fn __register_methods(builder: &mut ::godot::builder::ClassBuilder<Self>) {
+                #( #method_registrations )*
+
+                #(
+                    builder.signal(#signal_name_strs, &[]);
+                )*
+
+                #(
+                    builder.constant(#constant_name_strs, Self::#constants as i64);
+                )*
+            }
+        }
+    };
+
+    Ok(result)
+}
+
+/// Registers a `#[func]` through `ClassBuilder::method()`, forwarding to the method via a closure
+fn make_builder_method(signature: &Function) -> Result<TokenStream, Error> {
+    let method_name = &signature.name;
+    let method_name_str = method_name.to_string();
+
+    let mut has_receiver = false;
+    let mut param_names = vec![];
+    let mut param_types = vec![];
+    for (param, _punct) in signature.params.inner.iter() {
+        match param {
+            FnParam::Receiver(_) => has_receiver = true,
+            FnParam::Typed(param) => {
+                if !param.attributes.is_empty() {
+                    return bail(
+                        "#[func]: parameter attributes like #[opt] are not supported in generic impl blocks",
+                        &param.name,
+                    );
+                }
+                param_names.push(param.name.clone());
+                param_types.push(param.ty.clone());
+            }
+        }
+    }
+
+    if !has_receiver {
+        return bail(
+            "#[func]: methods must take `&self` or `&mut self`",
+            method_name,
+        );
+    }
+
+    Ok(quote! {
+        builder
+            .method(#method_name_str, |this: &mut Self, #( #param_names: #param_types ),*| {
+                this.#method_name( #( #param_names ),* )
+            })
+            .done();
+    })
+}
+
 fn process_godot_fns(decl: &mut Impl) -> Result<(Vec<Function>, Vec<Ident>), Error> {
     let mut func_signatures = vec![];
     let mut signal_idents = vec![]; // TODO consider signature
@@ -171,6 +267,39 @@ fn process_godot_fns(decl: &mut Impl) -> Result<(Vec<Function>, Vec<Ident>), Err
     Ok((func_signatures, signal_idents))
 }
 
+/// Collects `#[constant]` associated consts and removes the attribute. The consts themselves stay in the impl.
+fn process_godot_constants(decl: &mut Impl) -> Result<Vec<Ident>, Error> {
+    let mut constant_idents = vec![];
+
+    for item in decl.body_items.iter_mut() {
+        let constant = if let ImplMember::Constant(constant) = item {
+            constant
+        } else {
+            continue;
+        };
+
+        let attr_index = constant
+            .attributes
+            .iter()
+            .position(|attr| util::path_is_single(&attr.path, "constant"));
+
+        if let Some(index) = attr_index {
+            if !constant.attributes[index]
+                .value
+                .get_value_tokens()
+                .is_empty()
+            {
+                bail("#[constant] does not take any arguments", &constant.name)?;
+            }
+
+            constant.attributes.remove(index);
+            constant_idents.push(constant.name.clone());
+        }
+    }
+
+    Ok(constant_idents)
+}
+
 fn extract_attributes(method: &Function) -> Result<Option<BoundAttr>, Error> {
     let mut found = None;
     for (index, attr) in method.attributes.iter().enumerate() {
@@ -214,15 +343,58 @@ fn extract_attributes(method: &Function) -> Result<Option<BoundAttr>, Error> {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
-/// Codegen for `#[godot_api] impl GodotExt for MyType`
+/// Codegen for `#[godot_api] impl GodotExt for MyType` and `#[godot_api] impl NodeVirtual for MyType`
+///
+/// Also for generic `impl<T> NodeVirtual for MyType<T>`, whose callbacks are registered by each `#[godot_alias]`.
 fn transform_trait_impl(original_impl: Impl) -> Result<TokenStream, Error> {
-    let class_name = util::validate_impl(&original_impl, Some("GodotExt"), "godot_api")?;
-    let class_name_str = class_name.to_string();
+    let is_generic = original_impl.impl_generic_params.is_some();
+    let class_name = if is_generic {
+        // Generic arguments are allowed here, checked by the compiler
+        quote! { Self }
+    } else {
+        let class_name = util::validate_impl(&original_impl, None, "godot_api")?;
+        quote! { #class_name }
+    };
+
+    // Generic classes only implement GodotClass for instantiations with a #[godot_alias]
+    let impl_params = &original_impl.impl_generic_params;
+    let self_ty = &original_impl.self_ty;
+    let where_clause = if is_generic {
+        util::prepend_where_bound(
+            &original_impl.where_clause,
+            quote! { #self_ty: ::godot::obj::cap::ClassAlias },
+        )
+    } else {
+        TokenStream::new()
+    };
+
+    // Signatures and the Base class are checked by the compiler, as the trait declares all overridable methods
+    let trait_path = original_impl.trait_ty.as_ref().unwrap(); // unwrap: already checked outside
+    let is_virtual_trait = util::extract_typename(trait_path).map_or(false, |seg| {
+        let name = seg.ident.to_string();
+        name == "GodotExt" || name.ends_with("Virtual")
+    });
+    if !is_virtual_trait {
+        return bail(
+            "#[godot_api] for trait impls requires trait to be `GodotExt` or a virtual trait like `NodeVirtual`",
+            &original_impl,
+        );
+    }
 
     let mut godot_init_impl = TokenStream::new();
     let mut register_fn = quote! { None };
+    let mut register_class_impl = TokenStream::new();
     let mut create_fn = quote! { None };
     let mut to_string_fn = quote! { None };
+    let mut to_string_impl = TokenStream::new();
+    let mut on_notification_fn = quote! { None };
+    let mut on_notification_impl = TokenStream::new();
+    let mut get_property_fn = quote! { None };
+    let mut set_property_fn = quote! { None };
+    let mut property_list_fn = quote! { None };
+    let mut property_can_revert_fn = quote! { None };
+    let mut property_get_revert_fn = quote! { None };
+    let mut property_impls = vec![];
     let mut virtual_methods = vec![];
     let mut virtual_method_names = vec![];
 
@@ -241,13 +413,18 @@ fn transform_trait_impl(original_impl: Impl) -> Result<TokenStream, Error> {
                 register_fn = quote! { Some(#prv::ErasedRegisterFn {
                     raw: #prv::callbacks::register_class_by_builder::<#class_name>
                 }) };
+                register_class_impl = quote! {
+                    fn __register_class(builder: &mut ::godot::builder::ClassBuilder<Self>) {
+                        <Self as #trait_path>::register_class(builder)
+                    }
+                };
             }
 
             "init" => {
                 godot_init_impl = quote! {
-                    impl ::godot::obj::cap::GodotInit for #class_name {
+                    impl #impl_params ::godot::obj::cap::GodotInit for #self_ty #where_clause {
                         fn __godot_init(base: ::godot::obj::Base<Self::Base>) -> Self {
-                            <Self as ::godot::bind::GodotExt>::init(base)
+                            <Self as #trait_path>::init(base)
                         }
                     }
                 };
@@ -256,55 +433,152 @@ fn transform_trait_impl(original_impl: Impl) -> Result<TokenStream, Error> {
 
             "to_string" => {
                 to_string_fn = quote! { Some(#prv::callbacks::to_string::<#class_name>) };
+                to_string_impl = quote! {
+                    fn __godot_to_string(&self) -> ::godot::builtin::GodotString {
+                        <Self as #trait_path>::to_string(self)
+                    }
+                };
             }
 
-            // Other virtual methods, like ready, process etc.
-            known_name if VIRTUAL_METHOD_NAMES.contains(&known_name) => {
+            "on_notification" => {
+                on_notification_fn =
+                    quote! { Some(#prv::callbacks::on_notification::<#class_name>) };
+                on_notification_impl = quote! {
+                    fn __godot_notification(&mut self, what: i32) {
+                        <Self as #trait_path>::on_notification(self, what.into())
+                    }
+                };
+            }
+
+            "get_property" => {
+                get_property_fn = quote! { Some(#prv::callbacks::get_property::<#class_name>) };
+                property_impls.push(quote! {
+                    fn __godot_get_property(&self, name: &::godot::builtin::StringName) -> Option<::godot::builtin::Variant> {
+                        <Self as #trait_path>::get_property(self, name)
+                    }
+                });
+            }
+
+            "set_property" => {
+                set_property_fn = quote! { Some(#prv::callbacks::set_property::<#class_name>) };
+                property_impls.push(quote! {
+                    fn __godot_set_property(&mut self, name: &::godot::builtin::StringName, value: ::godot::builtin::Variant) -> bool {
+                        <Self as #trait_path>::set_property(self, name, value)
+                    }
+                });
+            }
+
+            "property_list" => {
+                property_list_fn = quote! { Some(#prv::callbacks::property_list::<#class_name>) };
+                property_impls.push(quote! {
+                    fn __godot_property_list(&self) -> Vec<::godot::builtin::meta::PropertyInfo> {
+                        <Self as #trait_path>::property_list(self)
+                    }
+                });
+            }
+
+            "property_can_revert" => {
+                property_can_revert_fn =
+                    quote! { Some(#prv::callbacks::property_can_revert::<#class_name>) };
+                property_impls.push(quote! {
+                    fn __godot_property_can_revert(&self, name: &::godot::builtin::StringName) -> bool {
+                        <Self as #trait_path>::property_can_revert(self, name)
+                    }
+                });
+            }
+
+            "property_get_revert" => {
+                property_get_revert_fn =
+                    quote! { Some(#prv::callbacks::property_get_revert::<#class_name>) };
+                property_impls.push(quote! {
+                    fn __godot_property_get_revert(&self, name: &::godot::builtin::StringName) -> Option<::godot::builtin::Variant> {
+                        <Self as #trait_path>::property_get_revert(self, name)
+                    }
+                });
+            }
+
+            // Other virtual methods, like ready, process, input etc.
+            // Methods not declared in the trait are rejected by the compiler.
+            _ => {
                 let method = util::reduce_to_signature(method);
 
                 // Godot-facing name begins with underscore
                 virtual_method_names.push(format!("_{method_name}"));
                 virtual_methods.push(method);
             }
+        }
+    }
 
-            // Unknown methods which are declared inside trait impl are not supported (possibly compiler catches those first anyway)
-            other_name => {
-                return bail(
-                    format!("Unsupported GodotExt method: {}", other_name),
-                    &method.name,
-                )
-            }
+    let user_virtuals = quote! {
+        #prv::PluginComponent::UserVirtuals {
+            user_register_fn: #register_fn,
+            user_create_fn: #create_fn,
+            user_to_string_fn: #to_string_fn,
+            user_on_notification_fn: #on_notification_fn,
+            user_get_property_fn: #get_property_fn,
+            user_set_property_fn: #set_property_fn,
+            user_property_list_fn: #property_list_fn,
+            user_property_can_revert_fn: #property_can_revert_fn,
+            user_property_get_revert_fn: #property_get_revert_fn,
+            get_virtual_fn: #prv::callbacks::get_virtual::<#class_name>,
         }
+    };
+
+    let (impl_tokens, registration);
+    if is_generic {
+        // The trait's GodotClass supertrait only holds for aliased instantiations, so the user's impl needs the bound, too
+        impl_tokens = util::add_where_bound(
+            original_impl.to_token_stream(),
+            quote! { #self_ty: ::godot::obj::cap::ClassAlias },
+        );
+        registration = quote! {
+            impl #impl_params ::godot::obj::cap::GenericVirtuals for #self_ty #where_clause {
+                fn __virtuals() -> #prv::PluginComponent {
+                    #user_virtuals
+                }
+            }
+        };
+    } else {
+        let source = util::plugin_source(&class_name);
+        impl_tokens = original_impl.to_token_stream();
+        registration = quote! {
+            ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
+                class_name: <#class_name as ::godot::obj::GodotClass>::CLASS_NAME,
+                #source
+                component: #user_virtuals,
+            });
+        };
     }
 
     let result = quote! {
-        #original_impl
+        #impl_tokens
         #godot_init_impl
 
-        impl ::godot::private::You_forgot_the_attribute__godot_api for #class_name {}
+        impl #impl_params ::godot::private::You_forgot_the_attribute__godot_api for #self_ty #where_clause {}
 
-        impl ::godot::obj::cap::ImplementsGodotExt for #class_name {
+        impl #impl_params ::godot::obj::cap::ImplementsGodotExt for #self_ty #where_clause {
             fn __virtual_call(name: &str) -> ::godot::sys::GDNativeExtensionClassCallVirtual {
                 //println!("virtual_call: {}.{}", std::any::type_name::<Self>(), name);
 
+                // Callbacks invoke the methods through the trait
+                #[allow(unused_imports)]
+                use #trait_path as _;
+
                 match name {
                     #(
-                       #virtual_method_names => #prv::gdext_virtual_method_callback!(#class_name, #virtual_methods),
+                       #virtual_method_names => #prv::gdext_virtual_method_callback!(Self, #trait_path, #virtual_methods),
                     )*
                     _ => None,
                 }
             }
+
+            #register_class_impl
+            #to_string_impl
+            #on_notification_impl
+            #( #property_impls )*
         }
 
-        ::godot::sys::plugin_add!(__GODOT_PLUGIN_REGISTRY in #prv; #prv::ClassPlugin {
-            class_name: #class_name_str,
-            component: #prv::PluginComponent::UserVirtuals {
-                user_register_fn: #register_fn,
-                user_create_fn: #create_fn,
-                user_to_string_fn: #to_string_fn,
-                get_virtual_fn: #prv::callbacks::get_virtual::<#class_name>,
-            },
-        });
+        #registration
     };
 
     Ok(result)
@@ -0,0 +1,51 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::util;
+use crate::util::bail;
+use proc_macro2::TokenStream;
+use quote::quote;
+use venial::{Declaration, Error};
+
+pub fn transform(input: TokenStream) -> Result<TokenStream, Error> {
+    let input_decl = venial::parse_declaration(input)?;
+    let decl = match input_decl {
+        Declaration::Impl(decl) => decl,
+        _ => bail(
+            "#[godot_dyn] can only be applied on impl blocks",
+            input_decl,
+        )?,
+    };
+
+    if decl.impl_generic_params.is_some() {
+        bail(
+            "#[godot_dyn] currently does not support generic parameters",
+            &decl,
+        )?;
+    }
+
+    let trait_ty = match &decl.trait_ty {
+        Some(trait_ty) => trait_ty.clone(),
+        None => bail(
+            "#[godot_dyn] requires a trait impl, e.g. `impl MyTrait for MyClass`",
+            &decl,
+        )?,
+    };
+
+    // Any trait is accepted, so only Self is validated
+    let class_name = util::validate_impl(&decl, None, "godot_dyn")?;
+    let prv = quote! { ::godot::private };
+
+    let result = quote! {
+        #decl
+
+        ::godot::sys::plugin_add!(__GODOT_DYN_REGISTRY in #prv;
+            #prv::DynTraitImpl::new::<#class_name, dyn #trait_ty>(|obj| obj)
+        );
+    };
+
+    Ok(result)
+}
@@ -8,21 +8,96 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 
 mod derive_godot_class;
+mod derive_godot_enum;
 mod gdextension;
+mod godot_alias;
 mod godot_api;
+mod godot_dyn;
 mod itest;
 mod util;
 
-#[proc_macro_derive(GodotClass, attributes(class, property, export, base, signal))]
+#[proc_macro_derive(GodotClass, attributes(class, property, export, base, signal, init))]
 pub fn derive_native_class(input: TokenStream) -> TokenStream {
     translate(input, derive_godot_class::transform)
 }
 
+/// Exposes a fieldless Rust enum to Godot, as integer with an enum hint.
+///
+/// Implements `EngineEnum`, `ToVariant`, `FromVariant` and `VariantMetadata`, so the enum can be used in `#[func]`
+/// signatures and properties. The enum must also derive `Copy`, `Clone` and `Debug`.
+///
+/// With `#[godot_enum(class = MyClass)]`, the enumerators are additionally registered as constants of `MyClass`,
+/// accessible as `MyClass.Direction.Up` in GDScript. Add `bitfield` for flags that can be combined.
+/// ```ignore
+/// #[derive(GodotEnum, Copy, Clone, Debug)]
+/// #[godot_enum(class = Player)]
+/// enum Direction {
+///     Up,
+///     Down = 5,
+/// }
+/// ```
+#[proc_macro_derive(GodotEnum, attributes(godot_enum))]
+pub fn derive_godot_enum(input: TokenStream) -> TokenStream {
+    translate(input, derive_godot_enum::transform)
+}
+
 #[proc_macro_attribute]
 pub fn godot_api(_meta: TokenStream, input: TokenStream) -> TokenStream {
     translate(input, godot_api::transform)
 }
 
+/// Registers a concrete instantiation of a generic class, under the name of the alias or `rename`.
+///
+/// The generic class can have at most one generic `#[godot_api]` impl block, e.g. `impl<T> Pool<T>`; a second one
+/// fails to compile, naming this restriction. Any number of blocks can be added for individual instantiations,
+/// e.g. `impl Pool<Enemy>`. Virtual methods like `init`, `to_string` or `ready` can be overridden in a generic
+/// trait impl, e.g. `#[godot_api] impl<T> RefCountedVirtual for Pool<T>`.
+/// ```ignore
+/// #[derive(GodotClass)]
+/// #[class(init, base=RefCounted)]
+/// struct Pool<T: 'static> {
+///     items: Vec<T>,
+/// }
+///
+/// #[godot_api]
+/// impl<T: 'static> Pool<T> {
+///     #[func]
+///     fn size(&self) -> i64 {
+///         self.items.len() as i64
+///     }
+/// }
+///
+/// #[godot_alias(rename = "PoolOfEnemy")]
+/// type EnemyPool = Pool<Enemy>;
+/// ```
+#[proc_macro_attribute]
+pub fn godot_alias(meta: TokenStream, input: TokenStream) -> TokenStream {
+    translate_meta(meta, input, godot_alias::transform)
+}
+
+/// Registers a trait impl of a user class, so that it can be used through `Gd::try_dyn()`.
+///
+/// Typical usage:
+/// ```ignore
+/// trait Damageable {
+///     fn take_damage(&mut self, amount: i32);
+/// }
+///
+/// #[godot_dyn]
+/// impl Damageable for Enemy {
+///     fn take_damage(&mut self, amount: i32) { ... }
+/// }
+///
+/// let mut node: Gd<Node> = ...;
+/// if let Some(mut damageable) = node.try_dyn::<dyn Damageable>() {
+///     damageable.take_damage(10);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn godot_dyn(_meta: TokenStream, input: TokenStream) -> TokenStream {
+    translate(input, godot_dyn::transform)
+}
+
 /// Similar to `#[test]`, but runs an integration test with Godot.
 ///
 /// Transforms the `fn` into one returning `bool` (success of the test), which must be called explicitly.
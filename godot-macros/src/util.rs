@@ -7,11 +7,11 @@
 // Note: some code duplication with codegen crate
 
 use crate::ParseResult;
-use proc_macro2::{Ident, Literal, Span, TokenTree};
-use quote::format_ident;
+use proc_macro2::{Ident, Literal, Span, TokenStream, TokenTree};
 use quote::spanned::Spanned;
+use quote::{format_ident, quote, ToTokens};
 use std::collections::HashMap;
-use venial::{Error, Function, Impl};
+use venial::{Error, Function, Impl, WhereClause};
 
 pub fn ident(s: &str) -> Ident {
     format_ident!("{}", s)
@@ -39,6 +39,54 @@ pub fn reduce_to_signature(function: &Function) -> Function {
     reduced
 }
 
+/// Prepends `bound` to the predicates of an existing where-clause, e.g. `where MyClass<T>: GodotClass, T: Debug`.
+pub(crate) fn prepend_where_bound(
+    where_clause: &Option<WhereClause>,
+    bound: TokenStream,
+) -> TokenStream {
+    // Skip the `where` keyword of the existing clause
+    let predicates: TokenStream = where_clause
+        .as_ref()
+        .map(|clause| clause.to_token_stream().into_iter().skip(1).collect())
+        .unwrap_or_default();
+
+    quote! { where #bound, #predicates }
+}
+
+/// Adds `bound` to the where-clause of an item like `impl<T> Trait for MyClass<T> where T: Debug { ... }`.
+///
+/// Works on tokens, inserting right after `where`, or before the body if there is no where-clause yet.
+pub(crate) fn add_where_bound(item: TokenStream, bound: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    let body = tokens.pop().expect("item has a body");
+
+    let where_pos = tokens
+        .iter()
+        .position(|tk| matches!(tk, TokenTree::Ident(ident) if ident == "where"));
+
+    let header: TokenStream = match where_pos {
+        Some(pos) => {
+            let (before, after) = tokens.split_at(pos + 1);
+            let (before, after) = (before.iter(), after.iter());
+            quote! { #(#before)* #bound, #(#after)* }
+        }
+        None => {
+            let tokens = tokens.iter();
+            quote! { #(#tokens)* where #bound }
+        }
+    };
+
+    quote! { #header #body }
+}
+
+/// Fields of `ClassPlugin` identifying the Rust type and invocation site, for registration errors
+pub(crate) fn plugin_source(rust_ty: &impl ToTokens) -> TokenStream {
+    quote! {
+        rust_type: ::std::any::type_name::<#rust_ty>(),
+        source_location: concat!(file!(), ":", line!()),
+    }
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // Key-value parsing of proc attributes
 
@@ -238,7 +286,7 @@ pub(crate) fn validate_impl(
 }
 
 /// Gets the right-most type name in the path
-fn extract_typename(ty: &venial::TyExpr) -> Option<venial::PathSegment> {
+pub(crate) fn extract_typename(ty: &venial::TyExpr) -> Option<venial::PathSegment> {
     match ty.as_path() {
         Some(mut path) => path.segments.pop(),
         _ => None,
@@ -5,7 +5,7 @@
  */
 
 #[doc(inline)]
-pub use godot_core::{builtin, engine, log, obj, sys};
+pub use godot_core::{builder, builtin, engine, log, obj, sys};
 
 /// Facilities for initializing and terminating the GDExtension library.
 pub mod init {
@@ -20,7 +20,7 @@ pub mod bind {
     pub use godot_core::bind::*;
 
     // Re-exports
-    pub use godot_macros::{godot_api, GodotClass};
+    pub use godot_macros::{godot_alias, godot_api, godot_dyn, GodotClass, GodotEnum};
 }
 
 /// Testing facilities (unstable).
@@ -34,7 +34,7 @@ pub use godot_core::private;
 
 /// Often-imported symbols.
 pub mod prelude {
-    pub use super::bind::{godot_api, GodotClass, GodotExt};
+    pub use super::bind::{godot_alias, godot_api, godot_dyn, GodotClass, GodotEnum, GodotExt};
     pub use super::builtin::*;
     pub use super::engine::{
         load, try_load, utilities, AudioStreamPlayer, Camera2D, Camera3D, Input, Node, Node2D,
@@ -42,9 +42,12 @@ pub mod prelude {
     };
     pub use super::init::{gdextension, ExtensionLayer, ExtensionLibrary, InitHandle, InitLevel};
     pub use super::log::*;
-    pub use super::obj::{Base, Gd, GdMut, GdRef, GodotClass, Inherits, InstanceId, Share};
+    pub use super::obj::{
+        Base, Gd, GdMut, GdRef, GodotClass, Inherits, InstanceId, OnReady, Share,
+    };
 
     // Make trait methods available
     pub use super::engine::NodeExt as _;
     pub use super::obj::EngineEnum as _;
+    pub use super::obj::WithBaseField as _;
 }
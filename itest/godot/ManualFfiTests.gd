@@ -9,6 +9,7 @@ func run() -> bool:
 	var ok = true
 	#ok = ok && test_missing_init()
 	ok = ok && test_to_string()
+	ok = ok && test_constants()
 
 	print("[GD] ManualFfi tested (passed=", ok, ")")
 	return ok
@@ -24,4 +25,11 @@ func test_to_string() -> bool:
 
 	print("to_string: ", s)
 	print("to_string: ", ffi)
-	return true
\ No newline at end of file
+	return true
+
+func test_constants() -> bool:
+	var ok = EnumTest.MAX_HP == 100
+	ok = ok && EnumTest.Up == 10
+	ok = ok && EnumTest.Direction.Down == 11
+	print("constants: ", ok)
+	return ok
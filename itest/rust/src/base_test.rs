@@ -13,6 +13,9 @@ pub(crate) fn run() -> bool {
     ok &= base_deref();
     ok &= base_display();
     ok &= base_debug();
+    ok &= base_mut_reentrant_call();
+    ok &= base_reentrant_call_without_base_mut();
+    ok &= base_bind_conflict_message();
 
     ok
 }
@@ -81,9 +84,76 @@ fn base_debug() {
     obj.free();
 }
 
+#[itest]
+fn base_mut_reentrant_call() {
+    let obj = Gd::<ReentrantHolder>::new_default();
+    let mut node = obj.share().upcast::<Node>();
+
+    node.call("increment_via_base_mut".into(), &[]);
+    assert_eq!(obj.bind().counter, 1);
+
+    node.free();
+}
+
+#[itest]
+fn base_reentrant_call_without_base_mut() {
+    let obj = Gd::<ReentrantHolder>::new_default();
+    let mut node = obj.share().upcast::<Node>();
+
+    // Inner call fails (instance still bound by outer call) and is reported as error, so counter is unchanged
+    node.call("increment_via_base".into(), &[]);
+    assert_eq!(obj.bind().counter, 0);
+
+    node.free();
+}
+
+#[itest]
+fn base_bind_conflict_message() {
+    let mut obj = Gd::<ReentrantHolder>::new_default();
+    let mut other = obj.share();
+
+    {
+        let _guard = obj.bind();
+
+        let err = other.try_bind_mut().expect_err("already bound");
+        let message = err.to_string();
+        assert!(message.contains("ReentrantHolder"), "{message}");
+        assert!(message.contains("Gd::bind() at"), "{message}");
+        assert!(message.contains("base_test.rs"), "{message}");
+    }
+
+    obj.free();
+}
+
 #[derive(GodotClass)]
 #[class(init, base=Node2D)]
 struct BaseHolder {
     #[base]
     base: Base<Node2D>,
 }
+
+#[derive(GodotClass)]
+#[class(init, base=Node)]
+struct ReentrantHolder {
+    #[base]
+    base: Base<Node>,
+    counter: i64,
+}
+
+#[godot_api]
+impl ReentrantHolder {
+    #[func]
+    fn increment(&mut self) {
+        self.counter += 1;
+    }
+
+    #[func]
+    fn increment_via_base_mut(&mut self) {
+        self.base_mut().call("increment".into(), &[]);
+    }
+
+    #[func]
+    fn increment_via_base(&mut self) {
+        self.base.call("increment".into(), &[]);
+    }
+}
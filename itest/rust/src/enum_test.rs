@@ -5,15 +5,53 @@
  */
 
 use crate::itest;
+use godot::bind::{godot_api, GodotClass, GodotEnum};
+use godot::builtin::{FromVariant, StringName, ToVariant, Variant};
 use godot::engine::input::CursorShape;
-use godot::engine::{file_access, os};
+use godot::engine::{file_access, os, Object, RefCounted};
+use godot::obj::{Base, EngineEnum, Gd};
 use std::collections::HashSet;
 
+#[derive(GodotClass, Debug)]
+#[class(init, base=RefCounted)]
+struct EnumTest {
+    #[base]
+    base: Base<RefCounted>,
+}
+
+#[godot_api]
+impl EnumTest {
+    #[constant]
+    const MAX_HP: i64 = 100;
+
+    #[func]
+    fn opposite(&mut self, direction: Direction) -> Direction {
+        match direction {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
+
+#[derive(GodotEnum, Copy, Clone, Debug, Eq, PartialEq)]
+#[godot_enum(class = EnumTest)]
+enum Direction {
+    Left,
+    Right,
+    Up = 10,
+    Down,
+}
+
 pub fn run() -> bool {
     let mut ok = true;
     ok &= enum_ords_correct();
     ok &= enum_equality();
     ok &= enum_hash();
+    ok &= user_enum_ords();
+    ok &= user_enum_variant_conversion();
+    ok &= user_enum_func_param();
     ok
 }
 
@@ -67,3 +105,38 @@ fn enum_hash() {
 
     assert_eq!(months.len(), 12);
 }
+
+#[itest]
+fn user_enum_ords() {
+    assert_eq!(Direction::Left.ord(), 0);
+    assert_eq!(Direction::Right.ord(), 1);
+    assert_eq!(Direction::Up.ord(), 10);
+    assert_eq!(Direction::Down.ord(), 11);
+
+    assert_eq!(Direction::try_from_ord(10), Some(Direction::Up));
+    assert_eq!(Direction::try_from_ord(2), None);
+    assert_eq!(EnumTest::MAX_HP, 100);
+}
+
+#[itest]
+fn user_enum_variant_conversion() {
+    let variant = Direction::Down.to_variant();
+    assert_eq!(variant, 11.to_variant());
+    assert_eq!(
+        Direction::try_from_variant(&variant).ok(),
+        Some(Direction::Down)
+    );
+
+    // Ordinals without enumerator and non-integer variants are rejected
+    assert!(Direction::try_from_variant(&5.to_variant()).is_err());
+    assert!(Direction::try_from_variant(&Variant::nil()).is_err());
+}
+
+#[itest]
+fn user_enum_func_param() {
+    let obj = Gd::<EnumTest>::new_default();
+    let mut object = obj.upcast::<Object>();
+
+    let result = object.call(StringName::from("opposite"), &[Direction::Up.to_variant()]);
+    assert_eq!(result, Direction::Down.to_variant());
+}
@@ -0,0 +1,231 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::itest;
+use godot::bind::{godot_alias, godot_api, GodotClass};
+use godot::builtin::{GodotString, StringName, ToVariant};
+use godot::engine::notify::NodeNotification;
+use godot::engine::virtuals::NodeVirtual;
+use godot::engine::{Node, Object};
+use godot::obj::{Base, Gd, GodotClass};
+
+pub fn run() -> bool {
+    let mut ok = true;
+    ok &= godot_api_multiple_impl_blocks();
+    ok &= godot_api_generic_class_alias();
+    ok &= godot_api_generic_class_concrete_block();
+    ok &= godot_api_generic_class_without_generic_block();
+    ok &= godot_api_generic_class_virtuals();
+    ok
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Tests
+
+#[itest]
+fn godot_api_multiple_impl_blocks() {
+    let obj = Gd::<SplitApi>::new_default();
+    let mut object = obj.upcast::<Object>();
+
+    // Both blocks are registered, neither overwrites the other
+    assert!(object.has_method(StringName::from("first")));
+    assert!(object.has_method(StringName::from("second")));
+
+    let first = object.call(StringName::from("first"), &[]);
+    let second = object.call(StringName::from("second"), &[5.to_variant()]);
+    assert_eq!(first, 1.to_variant());
+    assert_eq!(second, 7.to_variant());
+}
+
+#[itest]
+fn godot_api_generic_class_alias() {
+    assert_eq!(<IntStack as GodotClass>::CLASS_NAME, "StackOfInt");
+    assert_eq!(<FloatStack as GodotClass>::CLASS_NAME, "FloatStack");
+
+    let obj = Gd::<IntStack>::new_default();
+    let mut object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("StackOfInt"));
+
+    object.call(StringName::from("push_default"), &[]);
+    object.call(StringName::from("push_default"), &[]);
+    let size = object.call(StringName::from("size"), &[]);
+    assert_eq!(size, 2.to_variant());
+
+    // Each instantiation is a separate class
+    let obj = Gd::<FloatStack>::new_default();
+    let object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("FloatStack"));
+    assert!(object.has_method(StringName::from("size")));
+}
+
+#[itest]
+fn godot_api_generic_class_concrete_block() {
+    let obj = Gd::<IntStack>::new_default();
+    let mut object = obj.upcast::<Object>();
+    object.call(StringName::from("push_int"), &[4.to_variant()]);
+    object.call(StringName::from("push_int"), &[6.to_variant()]);
+
+    let sum = object.call(StringName::from("sum"), &[]);
+    assert_eq!(sum, 10.to_variant());
+
+    // Methods of a concrete block are only registered for that instantiation
+    let obj = Gd::<FloatStack>::new_default();
+    let object = obj.upcast::<Object>();
+    assert!(!object.has_method(StringName::from("sum")));
+}
+
+#[itest]
+fn godot_api_generic_class_without_generic_block() {
+    // Only a concrete block
+    let obj = Gd::<IntHolder>::new_default();
+    let mut object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("IntHolder"));
+    assert_eq!(
+        object.call(StringName::from("get_value"), &[]),
+        0.to_variant()
+    );
+
+    // No #[godot_api] block at all
+    let obj = Gd::<BoolHolder>::new_default();
+    let object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("BoolHolder"));
+    assert!(!object.has_method(StringName::from("get_value")));
+}
+
+#[itest]
+fn godot_api_generic_class_virtuals() {
+    // Constructed through the generic init() override
+    let obj = Gd::<IntReadyCounter>::new_default();
+    assert_eq!(obj.bind().ready_calls, 0);
+
+    let mut node = obj.share().upcast::<Node>();
+    node.notify(NodeNotification::READY);
+    assert_eq!(obj.bind().ready_calls, 1);
+    assert_eq!(format!("{node}"), "ReadyCounter[tag=0, ready_calls=1]");
+
+    node.free();
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(GodotClass, Debug)]
+#[class(init, base=RefCounted)]
+struct SplitApi {}
+
+#[godot_api]
+impl SplitApi {
+    #[func]
+    fn first(&self) -> i64 {
+        1
+    }
+}
+
+#[godot_api]
+impl SplitApi {
+    #[func]
+    fn second(&mut self, value: i64) -> i64 {
+        value + 2
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(GodotClass, Debug)]
+#[class(init, base=RefCounted)]
+struct Stack<T: Default + std::fmt::Debug + 'static> {
+    items: Vec<T>,
+}
+
+#[godot_api]
+impl<T: Default + std::fmt::Debug + 'static> Stack<T> {
+    #[func]
+    fn push_default(&mut self) {
+        self.items.push(T::default());
+    }
+
+    #[func]
+    fn size(&self) -> i64 {
+        self.items.len() as i64
+    }
+}
+
+#[godot_api]
+impl Stack<i64> {
+    #[func]
+    fn push_int(&mut self, value: i64) {
+        self.items.push(value);
+    }
+
+    #[func]
+    fn sum(&self) -> i64 {
+        self.items.iter().sum()
+    }
+}
+
+#[godot_alias(rename = "StackOfInt")]
+type IntStack = Stack<i64>;
+
+#[godot_alias]
+type FloatStack = Stack<f64>;
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(GodotClass, Debug)]
+#[class(init, base=RefCounted)]
+struct Holder<T: Default + std::fmt::Debug + 'static> {
+    value: T,
+}
+
+#[godot_api]
+impl Holder<i64> {
+    #[func]
+    fn get_value(&self) -> i64 {
+        self.value
+    }
+}
+
+#[godot_alias]
+type IntHolder = Holder<i64>;
+
+#[godot_alias]
+type BoolHolder = Holder<bool>;
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(GodotClass, Debug)]
+#[class(base=Node)]
+struct ReadyCounter<T: Default + std::fmt::Debug + 'static> {
+    #[base]
+    base: Base<Node>,
+    tag: T,
+    ready_calls: i64,
+}
+
+#[godot_api]
+impl<T: Default + std::fmt::Debug + 'static> NodeVirtual for ReadyCounter<T> {
+    fn init(base: Base<Node>) -> Self {
+        Self {
+            base,
+            tag: T::default(),
+            ready_calls: 0,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.ready_calls += 1;
+    }
+
+    fn to_string(&self) -> GodotString {
+        format!(
+            "ReadyCounter[tag={:?}, ready_calls={}]",
+            self.tag, self.ready_calls
+        )
+        .into()
+    }
+}
+
+#[godot_alias]
+type IntReadyCounter = ReadyCounter<i64>;
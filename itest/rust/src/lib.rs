@@ -12,10 +12,14 @@ use std::panic::UnwindSafe;
 mod base_test;
 mod enum_test;
 mod gdscript_ffi_test;
+mod godot_api_test;
+mod node_path_test;
 mod node_test;
 mod object_test;
+mod serde_test;
 mod singleton_test;
 mod string_test;
+mod thread_test;
 mod utilities_test;
 mod variant_test;
 mod virtual_methods_test;
@@ -24,11 +28,15 @@ fn run_tests() -> bool {
     let mut ok = true;
     ok &= base_test::run();
     ok &= gdscript_ffi_test::run();
+    ok &= godot_api_test::run();
+    ok &= node_path_test::run();
     ok &= node_test::run();
     ok &= enum_test::run();
     ok &= object_test::run();
+    ok &= serde_test::run();
     ok &= singleton_test::run();
     ok &= string_test::run();
+    ok &= thread_test::run();
     ok &= utilities_test::run();
     ok &= variant_test::run();
     ok &= virtual_methods_test::run();
@@ -0,0 +1,100 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::itest;
+use godot::builtin::{GodotString, NodePath, StringName};
+use std::collections::HashSet;
+
+pub fn run() -> bool {
+    let mut ok = true;
+    ok &= node_path_names();
+    ok &= node_path_absolute_empty();
+    ok &= node_path_concatenated();
+    ok &= node_path_join();
+    ok &= node_path_eq_hash();
+    ok
+}
+
+#[itest]
+fn node_path_names() {
+    let path = NodePath::from("Path2D/PathFollow2D:position:x");
+
+    assert_eq!(path.get_name_count(), 2);
+    assert_eq!(path.get_name(0), StringName::from("Path2D"));
+    assert_eq!(path.get_name(1), StringName::from("PathFollow2D"));
+
+    assert_eq!(path.get_subname_count(), 2);
+    assert_eq!(path.get_subname(0), StringName::from("position"));
+    assert_eq!(path.get_subname(1), StringName::from("x"));
+
+    crate::expect_panic("name index out of bounds", || {
+        path.get_name(2);
+    });
+}
+
+#[itest]
+fn node_path_absolute_empty() {
+    assert!(NodePath::from("/root/Main").is_absolute());
+    assert!(!NodePath::from("Main/Child").is_absolute());
+
+    assert!(NodePath::from("").is_empty());
+    assert!(NodePath::default().is_empty());
+    assert!(!NodePath::from("Child").is_empty());
+}
+
+#[itest]
+fn node_path_concatenated() {
+    let path = NodePath::from("Path2D/PathFollow2D:position:x");
+
+    assert_eq!(
+        path.get_concatenated_names(),
+        StringName::from("Path2D/PathFollow2D")
+    );
+    assert_eq!(
+        path.get_concatenated_subnames(),
+        StringName::from("position:x")
+    );
+    assert_eq!(
+        path.get_as_property_path(),
+        NodePath::from(":position:x")
+    );
+}
+
+#[itest]
+fn node_path_join() {
+    let base = NodePath::from("Player");
+
+    assert_eq!(
+        base.join(&NodePath::from("Sprite2D:position")),
+        NodePath::from("Player/Sprite2D:position")
+    );
+    assert_eq!(
+        base.join(&NodePath::from(":modulate")),
+        NodePath::from("Player:modulate")
+    );
+    assert_eq!(
+        base.join(&NodePath::from("/root/Main")),
+        NodePath::from("/root/Main")
+    );
+    assert_eq!(base.join(&NodePath::default()), base);
+
+    crate::expect_panic("join onto path with subnames", || {
+        NodePath::from("Player:position").join(&NodePath::from("Child"));
+    });
+}
+
+#[itest]
+fn node_path_eq_hash() {
+    let a = NodePath::from("Parent/Child");
+    let b = NodePath::from(&GodotString::from("Parent/Child"));
+    let c = NodePath::from("Parent/Other");
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    let set: HashSet<NodePath> = [a, b, c].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
@@ -4,17 +4,44 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use crate::itest;
+use crate::{expect_panic, itest};
+use godot::bind::GodotClass;
 use godot::builtin::NodePath;
+use godot::engine::notify::NodeNotification;
 use godot::engine::{node, Node, Node3D, NodeExt};
 use godot::log::godot_print;
-use godot::obj::Share;
+use godot::obj::{Base, Gd, OnReady, Share};
+
+#[derive(GodotClass, Debug)]
+#[class(init, base=Node)]
+struct FieldInitTest {
+    #[base]
+    base: Base<Node>,
+
+    #[init(default = 42)]
+    health: i64,
+
+    #[init(default = vec![1, 2, 3])]
+    items: Vec<i32>,
+
+    #[init(node = "child")]
+    child: OnReady<Node3D>,
+
+    untouched: i32,
+}
 
 pub fn run() -> bool {
     let mut ok = true;
     ok &= node_print();
     ok &= node_get_node();
     ok &= node_get_node_fail();
+    ok &= node_owned_frees_on_drop();
+    ok &= node_owned_release();
+    ok &= node_owned_reparented();
+    ok &= node_owned_freed_elsewhere();
+    ok &= node_field_init();
+    ok &= node_field_init_ready();
+    ok &= node_field_init_ready_missing();
     //ok &= node_scene_tree();
     ok
 }
@@ -56,6 +83,10 @@ fn node_get_node() {
     let found = found.expect("try_get_node_as() returned Some(..)");
     assert_eq!(found.instance_id(), child_id);
 
+    // On Gd<U> with U derived from Node
+    let found = parent.get_node_as::<Node3D>(NodePath::from("child"));
+    assert_eq!(found.instance_id(), child_id);
+
     grandparent.free();
 }
 
@@ -70,6 +101,130 @@ fn node_get_node_fail() {
     child.free();
 }
 
+#[itest]
+fn node_owned_frees_on_drop() {
+    let node = Node3D::new_alloc();
+    let weak = node.downgrade();
+
+    {
+        let mut owned = node.into_owned();
+        owned.set_name("temporary".into());
+    }
+
+    assert!(weak.upgrade().is_none());
+}
+
+#[itest]
+fn node_owned_release() {
+    let owned = Node::new_alloc().into_owned();
+    let node: Gd<Node> = owned.release();
+
+    assert!(node.is_instance_valid());
+    node.free();
+}
+
+#[itest]
+fn node_owned_reparented() {
+    let mut parent = Node::new_alloc();
+    let child = Node::new_alloc().into_owned();
+    let weak = child.downgrade();
+
+    parent.add_child(
+        child.share(),
+        false,
+        node::InternalMode::INTERNAL_MODE_DISABLED,
+    );
+
+    // Parent took over ownership, child must survive
+    drop(child);
+    assert!(weak.upgrade().is_some());
+
+    parent.free();
+    assert!(weak.upgrade().is_none());
+}
+
+#[itest]
+fn node_owned_freed_elsewhere() {
+    let owned = Node::new_alloc().into_owned();
+    owned.share().free();
+
+    // Dropping the guard must not free again
+    drop(owned);
+
+    let node = Node::new_alloc();
+    let other = node.share();
+    node.free();
+    expect_panic("into_owned() on freed node", move || {
+        let _ = other.into_owned();
+    });
+}
+
+#[itest]
+fn node_field_init() {
+    let obj = Gd::<FieldInitTest>::new_default();
+    assert_eq!(obj.bind().health, 42);
+    assert_eq!(obj.bind().items, vec![1, 2, 3]);
+    assert_eq!(obj.bind().untouched, 0);
+    assert!(!obj.bind().child.is_resolved());
+
+    let mut child = Node3D::new_alloc();
+    child.set_name("child".into());
+    let child_id = child.instance_id();
+
+    let mut parent = obj.share().upcast::<Node>();
+    parent.add_child(
+        child.upcast(),
+        false,
+        node::InternalMode::INTERNAL_MODE_DISABLED,
+    );
+
+    // Looked up on first access
+    assert_eq!(obj.bind().child.instance_id(), child_id);
+    assert!(obj.bind().child.is_resolved());
+
+    parent.free();
+}
+
+#[itest]
+fn node_field_init_ready() {
+    let obj = Gd::<FieldInitTest>::new_default();
+
+    let mut child = Node3D::new_alloc();
+    child.set_name("child".into());
+    let child_id = child.instance_id();
+
+    let mut parent = obj.share().upcast::<Node>();
+    parent.add_child(
+        child.upcast(),
+        false,
+        node::InternalMode::INTERNAL_MODE_DISABLED,
+    );
+
+    // Resolved without accessing the field
+    parent.notify(NodeNotification::READY);
+    assert!(obj.bind().child.is_resolved());
+    assert_eq!(obj.bind().child.instance_id(), child_id);
+
+    parent.free();
+}
+
+#[itest]
+fn node_field_init_ready_missing() {
+    let obj = Gd::<FieldInitTest>::new_default();
+
+    // Logs an error instead of panicking inside the engine callback
+    let mut node = obj.share().upcast::<Node>();
+    node.notify(NodeNotification::READY);
+    assert!(!obj.bind().child.is_resolved());
+
+    let obj_copy = obj.share();
+    expect_panic("access to unresolved OnReady", move || {
+        let _ = obj_copy.bind().child.instance_id();
+    });
+
+    node.free();
+}
+
 /*
 #[itest]
 fn node_scene_tree() {
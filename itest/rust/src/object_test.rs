@@ -5,14 +5,16 @@
  */
 
 use crate::{expect_panic, itest};
-use godot::bind::{godot_api, GodotClass, GodotExt};
+use godot::bind::{godot_api, godot_dyn, GodotClass, GodotExt};
 use godot::builtin::{FromVariant, GodotString, StringName, ToVariant, Variant, Vector3};
-use godot::engine::{Node, Node3D, Object, RefCounted};
+use godot::engine::{ClassDB, Node, Node3D, Object, RefCounted};
+use godot::obj::leaks::{self, ObjectKind};
 use godot::obj::Share;
-use godot::obj::{Base, Gd, InstanceId};
+use godot::obj::{AccessError, Base, Gd, GodotClass, InstanceId};
 use godot::sys::GodotFfi;
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 // pub(crate) fn register() {
@@ -31,6 +33,8 @@ pub fn run() -> bool {
     ok &= object_debug();
     ok &= object_instance_id();
     ok &= object_instance_id_when_freed();
+    ok &= object_engine_use_after_free();
+    ok &= object_user_try_bind();
     ok &= object_from_invalid_instance_id();
     ok &= object_from_instance_id_inherits_type();
     ok &= object_from_instance_id_unrelated_type();
@@ -42,14 +46,34 @@ pub fn run() -> bool {
     ok &= object_engine_downcast();
     ok &= object_engine_bad_downcast();
     ok &= object_user_upcast();
+    ok &= object_engine_upcast_ref();
+    ok &= object_user_upcast_ref();
     ok &= object_user_downcast();
     ok &= object_user_bad_downcast();
     ok &= object_engine_manual_free();
     ok &= object_engine_manual_double_free();
     ok &= object_engine_refcounted_free();
+    ok &= object_dynamic_refcount_casts();
+    ok &= object_dynamic_manual_casts();
+    ok &= object_dynamic_user_casts();
+    ok &= object_dyn_trait();
+    ok &= object_dyn_trait_missing();
     ok &= object_user_share_drop();
     ok &= object_call_no_args();
     ok &= object_call_with_args();
+    ok &= object_weak_upgrade_refcounted();
+    ok &= object_weak_upgrade_manual();
+    ok &= object_weak_upgrade_user();
+    ok &= object_eq_identity();
+    ok &= object_eq_cross_type();
+    ok &= object_hash_ord();
+    ok &= object_hash_freed();
+    ok &= object_leak_snapshot_user();
+    ok &= object_leak_snapshot_engine();
+    ok &= object_class_rename();
+    ok &= object_class_flags();
+    ok &= object_class_parent();
+    ok &= object_user_with_base();
     ok
 }
 
@@ -161,6 +185,56 @@ fn object_instance_id_when_freed() {
     });
 }
 
+#[itest]
+fn object_engine_use_after_free() {
+    let node: Gd<Node3D> = Node3D::new_alloc();
+    let id = node.instance_id();
+    assert!(node.try_deref().is_ok());
+
+    node.share().free();
+
+    match node.try_deref() {
+        Err(AccessError::Freed {
+            class_name,
+            instance_id,
+            freed_at,
+        }) => {
+            assert_eq!(class_name, "Node3D");
+            assert_eq!(instance_id, Some(id));
+            if cfg!(debug_assertions) {
+                assert!(freed_at.contains("Gd::free()"), "{freed_at}");
+            }
+        }
+        other => panic!("expected freed error, got {other:?}"),
+    }
+
+    // Safeguards are enabled in debug builds
+    if cfg!(debug_assertions) {
+        expect_panic("method call on dead obj", || {
+            node.get_position();
+        });
+    }
+}
+
+#[itest]
+fn object_user_try_bind() {
+    let mut obj = user_object();
+    assert_eq!(obj.try_bind().map(|guard| guard.value), Ok(17943));
+
+    let mut other = obj.share();
+    let guard = obj.bind_mut();
+    assert!(matches!(
+        other.try_bind_mut(),
+        Err(AccessError::AlreadyBound {
+            class_name: "ObjPayload",
+            ..
+        })
+    ));
+    drop(guard);
+
+    assert!(other.try_bind_mut().is_ok());
+}
+
 #[itest]
 fn object_from_invalid_instance_id() {
     let id = InstanceId::try_from_i64(0xDEADBEEF).unwrap();
@@ -303,6 +377,35 @@ fn object_user_upcast() {
     assert_eq!(object.get_class(), GodotString::from("ObjPayload"));
 }
 
+#[itest]
+fn object_engine_upcast_ref() {
+    let mut node3d: Gd<Node3D> = Node3D::new_alloc();
+
+    node3d.upcast_mut::<Node>().set_name("renamed".into());
+    assert_eq!(
+        node3d.upcast_ref::<Node>().get_name(),
+        StringName::from("renamed")
+    );
+
+    // Indirect base
+    let object: &Object = node3d.upcast_ref::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("Node3D"));
+
+    node3d.free();
+}
+
+#[itest]
+fn object_user_upcast_ref() {
+    let obj = user_object();
+
+    // No reference count increment, unlike share().upcast()
+    let refc: &RefCounted = obj.upcast_ref::<RefCounted>();
+    assert_eq!(refc.get_reference_count(), 1);
+
+    let object: &Object = obj.upcast_ref::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("ObjPayload"));
+}
+
 #[itest]
 fn object_user_downcast() {
     let obj = user_object();
@@ -355,6 +458,61 @@ fn object_engine_refcounted_free() {
     expect_panic("calling free() on RefCounted obj", || node2.free())
 }
 
+#[itest]
+fn object_dynamic_refcount_casts() {
+    let refc = RefCounted::new();
+    assert_eq!(refc.get_reference_count(), 1);
+
+    let obj: Gd<Object> = refc.share().upcast();
+    assert_eq!(refc.get_reference_count(), 2);
+
+    let obj2 = obj.share();
+    assert_eq!(refc.get_reference_count(), 3);
+
+    // Reference is moved into the cast result
+    let back = obj.cast::<RefCounted>();
+    assert_eq!(refc.get_reference_count(), 3);
+
+    // Failed cast releases the reference
+    assert!(obj2.try_cast::<Node>().is_none());
+    assert_eq!(refc.get_reference_count(), 2);
+
+    drop(back);
+    assert_eq!(refc.get_reference_count(), 1);
+}
+
+#[itest]
+fn object_dynamic_manual_casts() {
+    let node = Node3D::new_alloc();
+    let obj: Gd<Object> = node.share().upcast();
+    let obj2 = obj.share();
+    drop(obj);
+
+    // Dropping Object-typed handles to manually managed objects must not destroy them
+    assert!(node.is_instance_valid());
+
+    let node2 = obj2.cast::<Node>();
+    node2.free();
+    assert!(!node.is_instance_valid());
+}
+
+#[itest]
+fn object_dynamic_user_casts() {
+    let drop_count = Rc::new(RefCell::new(0));
+    let tracker = Gd::new(Tracker {
+        drop_count: Rc::clone(&drop_count),
+    });
+
+    let obj: Gd<Object> = tracker.upcast();
+    let obj2 = obj.share();
+    drop(obj);
+    assert_eq!(*drop_count.borrow(), 0);
+
+    let tracker = obj2.cast::<Tracker>();
+    drop(tracker);
+    assert_eq!(*drop_count.borrow(), 1);
+}
+
 #[itest]
 fn object_user_share_drop() {
     let drop_count = Rc::new(RefCell::new(0));
@@ -404,6 +562,261 @@ fn object_call_with_args() {
     node.free();
 }
 
+#[itest]
+fn object_weak_upgrade_refcounted() {
+    let obj = RefCounted::new();
+    let weak = obj.downgrade();
+    assert_eq!(obj.get_reference_count(), 1);
+
+    let strong = weak.upgrade().expect("object alive");
+    assert_eq!(strong.instance_id(), obj.instance_id());
+    assert_eq!(obj.get_reference_count(), 2);
+
+    drop(strong);
+    drop(obj);
+    assert!(weak.upgrade().is_none());
+}
+
+#[itest]
+fn object_weak_upgrade_manual() {
+    let node = Node3D::new_alloc();
+    let weak = node.downgrade();
+
+    let as_node = weak.upgrade().expect("object alive").upcast::<Node>();
+    assert_eq!(as_node.instance_id(), weak.instance_id());
+
+    node.free();
+    assert!(weak.upgrade().is_none());
+}
+
+#[itest]
+fn object_weak_upgrade_user() {
+    let drop_count = Rc::new(RefCell::new(0));
+
+    let object: Gd<Tracker> = Gd::new(Tracker {
+        drop_count: Rc::clone(&drop_count),
+    });
+    let weak = object.downgrade();
+    assert_eq!(*drop_count.borrow(), 0);
+
+    // Weak references do not keep the object alive
+    drop(object);
+    assert_eq!(*drop_count.borrow(), 1);
+    assert!(weak.upgrade().is_none());
+
+    let payload = Gd::new(ObjPayload { value: 42 });
+    let weak = payload.downgrade();
+    assert_eq!(weak.upgrade().expect("object alive").bind().value, 42);
+}
+
+#[itest]
+fn object_eq_identity() {
+    let a = RefCounted::new();
+    let b = RefCounted::new();
+    let a2 = a.share();
+
+    assert_eq!(a, a2);
+    assert_ne!(a, b);
+    assert!(a.ptr_eq(&a2));
+    assert!(!a.ptr_eq(&b));
+
+    let payload = user_object();
+    assert_eq!(payload, payload.share());
+    assert_ne!(payload, user_object());
+}
+
+#[itest]
+fn object_eq_cross_type() {
+    let node3d = Node3D::new_alloc();
+    let node = node3d.share().upcast::<Node>();
+    let other = Node::new_alloc();
+
+    assert!(node3d == node);
+    assert!(node == node3d);
+    assert!(node3d != other);
+    assert!(node3d.ptr_eq(&node));
+
+    other.free();
+    node3d.free();
+}
+
+#[itest]
+fn object_hash_ord() {
+    let a = RefCounted::new();
+    let b = RefCounted::new();
+
+    let set: HashSet<Gd<RefCounted>> = [a.share(), b.share(), a.share()].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&a));
+    assert!(set.contains(&b));
+
+    let mut sorted = vec![b.share(), a.share()];
+    sorted.sort();
+    let expected = if a.instance_id() < b.instance_id() {
+        [&a, &b]
+    } else {
+        [&b, &a]
+    };
+    assert_eq!(sorted[0], *expected[0]);
+    assert_eq!(sorted[1], *expected[1]);
+}
+
+#[itest]
+fn object_hash_freed() {
+    let node = Node3D::new_alloc();
+    let other = Node3D::new_alloc();
+
+    let mut set: HashSet<Gd<Node3D>> = [node.share(), other.share()].into_iter().collect();
+    node.share().free();
+
+    // Freeing must not change hash or equality of elements already stored
+    assert!(set.contains(&node));
+    assert!(set.remove(&node));
+    assert!(!set.contains(&node));
+    assert_eq!(set.len(), 1);
+
+    other.free();
+}
+
+#[itest]
+fn object_dyn_trait() {
+    let obj = user_object();
+    let mut untyped: Gd<Object> = obj.share().upcast();
+
+    {
+        let mut damageable = untyped
+            .try_dyn::<dyn Damageable>()
+            .expect("ObjPayload implements Damageable");
+        assert_eq!(damageable.take_damage(43), 17900);
+    }
+    assert_eq!(obj.bind().value, 17900);
+
+    // Exclusive borrow, like bind_mut()
+    let _guard = obj.bind();
+    expect_panic("try_dyn() while bound", move || {
+        let _ = untyped.try_dyn::<dyn Damageable>();
+    });
+}
+
+#[itest]
+fn object_dyn_trait_missing() {
+    let mut node = Node3D::new_alloc();
+    assert!(node.try_dyn::<dyn Damageable>().is_none());
+    node.free();
+
+    let mut tracker: Gd<Object> = Gd::new(Tracker {
+        drop_count: Rc::new(RefCell::new(0)),
+    })
+    .upcast();
+    assert!(tracker.try_dyn::<dyn Damageable>().is_none());
+}
+
+#[itest]
+fn object_leak_snapshot_user() {
+    let before = leaks::snapshot();
+    let obj = user_object();
+    let id = obj.instance_id();
+
+    let during = before.diff(&leaks::snapshot());
+    assert_eq!(during.created.len(), 1);
+    assert!(during.destroyed.is_empty());
+
+    let live = &during.created[0];
+    assert_eq!(live.class_name, "ObjPayload");
+    assert_eq!(live.instance_id, Some(id));
+    assert_eq!(live.kind, ObjectKind::User);
+
+    drop(obj);
+    let after = before.diff(&leaks::snapshot());
+    assert!(after.is_empty(), "leaked objects:\n{after}");
+}
+
+#[itest]
+fn object_leak_snapshot_engine() {
+    leaks::set_track_engine_objects(true);
+
+    let before = leaks::snapshot();
+    let node = Node3D::new_alloc();
+    let id = node.instance_id();
+
+    let during = before.diff(&leaks::snapshot());
+    assert!(during
+        .created
+        .iter()
+        .any(|obj| obj.instance_id == Some(id) && obj.kind == ObjectKind::Engine));
+
+    node.free();
+    let after = before.diff(&leaks::snapshot());
+    assert!(after.is_empty(), "leaked objects:\n{after}");
+
+    leaks::set_track_engine_objects(false);
+}
+
+#[itest]
+fn object_class_rename() {
+    assert_eq!(
+        <RenamedPayload as GodotClass>::CLASS_NAME,
+        "ObjRenamedPayload"
+    );
+
+    let obj = Gd::<RenamedPayload>::new_default();
+    let mut object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("ObjRenamedPayload"));
+
+    // #[func] methods are registered under the Godot name
+    let result = object.call(StringName::from("get_value"), &[]);
+    assert_eq!(result, 7.to_variant());
+}
+
+#[itest]
+fn object_class_flags() {
+    assert!(<ToolPayload as GodotClass>::IS_TOOL);
+    assert!(!<RenamedPayload as GodotClass>::IS_TOOL);
+
+    // Abstract classes cannot be instantiated by Godot, but still from Rust
+    let class_db = ClassDB::singleton();
+    assert!(class_db.class_exists(StringName::from("ToolPayload")));
+    assert!(!class_db.can_instantiate(StringName::from("ToolPayload")));
+
+    let obj = Gd::<ToolPayload>::new_default();
+    let object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("ToolPayload"));
+
+    // Hidden classes are only left out of the editor dialog; Godot can still instantiate them
+    assert!(class_db.class_exists(StringName::from("HiddenPayload")));
+    assert!(class_db.can_instantiate(StringName::from("HiddenPayload")));
+
+    let variant = class_db.instantiate(StringName::from("HiddenPayload"));
+    let object = variant.to::<Gd<Object>>();
+    assert_eq!(object.get_class(), GodotString::from("HiddenPayload"));
+}
+
+#[itest]
+fn object_class_parent() {
+    let class_db = ClassDB::singleton();
+    let parent_of = |class: &str| class_db.get_parent_class(StringName::from(class));
+
+    // Base classes are registered under their Godot name, also when the derived class is renamed
+    assert_eq!(
+        parent_of("ObjRenamedPayload"),
+        StringName::from("RefCounted")
+    );
+    assert_eq!(parent_of("ObjWithBase"), StringName::from("RefCounted"));
+    assert_eq!(parent_of("ToolPayload"), StringName::from("RefCounted"));
+}
+
+#[itest]
+fn object_user_with_base() {
+    let obj = Gd::with_base(|base| ObjWithBase { base, hp: 30 });
+    assert_eq!(obj.bind().hp, 30);
+
+    // Base refers to the same object
+    assert_eq!(obj.bind().base.instance_id(), obj.instance_id());
+
+    let object = obj.upcast::<Object>();
+    assert_eq!(object.get_class(), GodotString::from("ObjWithBase"));
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
 #[inline(never)] // force to move "out of scope", can trigger potential dangling pointer errors
@@ -426,6 +839,49 @@ impl GodotExt for ObjPayload {
     }
 }
 
+trait Damageable {
+    /// Returns remaining value.
+    fn take_damage(&mut self, amount: i16) -> i16;
+}
+
+#[godot_dyn]
+impl Damageable for ObjPayload {
+    fn take_damage(&mut self, amount: i16) -> i16 {
+        self.value -= amount;
+        self.value
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(GodotClass, Debug)]
+#[class(init, rename = "ObjRenamedPayload")]
+pub struct RenamedPayload {}
+
+#[godot_api]
+impl RenamedPayload {
+    #[func]
+    fn get_value(&mut self) -> i64 {
+        7
+    }
+}
+
+#[derive(GodotClass, Debug)]
+#[class(init, tool, abstract, hidden)]
+pub struct ToolPayload {}
+
+#[derive(GodotClass, Debug)]
+#[class(init, hidden, base=RefCounted)]
+pub struct HiddenPayload {}
+
+#[derive(GodotClass, Debug)]
+#[class(no_init, base=RefCounted)]
+pub struct ObjWithBase {
+    #[base]
+    base: Base<RefCounted>,
+    hp: i64,
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
 #[derive(GodotClass, Debug, Eq, PartialEq)]
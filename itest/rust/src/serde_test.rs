@@ -0,0 +1,149 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::itest;
+use godot::builtin::variant::encoding::Value;
+use godot::builtin::{
+    Array, Color, Dictionary, FromVariant, GodotString, NodePath, Quaternion, Rect2, StringName,
+    ToVariant, Transform2D, Transform3D, Variant, Vector2, Vector3i,
+};
+use godot::obj::InstanceId;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+pub fn run() -> bool {
+    let mut ok = true;
+    ok &= serde_math_types();
+    ok &= serde_opaque_math_types();
+    ok &= serde_strings();
+    ok &= serde_instance_id();
+    ok &= serde_variant();
+    ok &= serde_containers();
+    ok
+}
+
+#[itest]
+fn serde_math_types() {
+    assert_eq!(json(&Vector2::new(1.5, -2.0)), "[1.5,-2.0]");
+    roundtrip(Vector2::new(1.5, -2.0));
+    roundtrip(Vector3i::new(1, -2, 3));
+    roundtrip(Color::new(0.25, 0.5, 0.75, 1.0));
+}
+
+#[itest]
+fn serde_opaque_math_types() {
+    // Values are built by the engine, so the memory layout assumed by the serde impls is checked against Godot's
+    let rect: Rect2 = engine_value(Value::Rect2 {
+        position: [1.0, 2.0],
+        size: [3.0, 4.0],
+    });
+    assert_eq!(json(&rect), r#"{"position":[1.0,2.0],"size":[3.0,4.0]}"#);
+    roundtrip_variant(rect);
+
+    let quat: Quaternion = engine_value(Value::Quaternion([0.5, -0.5, 0.25, 0.75]));
+    assert_eq!(json(&quat), r#"{"x":0.5,"y":-0.5,"z":0.25,"w":0.75}"#);
+    roundtrip_variant(quat);
+
+    let transform: Transform2D =
+        engine_value(Value::Transform2D([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]));
+    assert_eq!(
+        json(&transform),
+        r#"{"x":[1.0,2.0],"y":[3.0,4.0],"origin":[5.0,6.0]}"#
+    );
+    roundtrip_variant(transform);
+
+    let transform: Transform3D = engine_value(Value::Transform3D {
+        basis: [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]],
+        origin: [10.0, 11.0, 12.0],
+    });
+    assert_eq!(
+        json(&transform),
+        r#"{"basis":[[1.0,2.0,3.0],[4.0,5.0,6.0],[7.0,8.0,9.0]],"origin":[10.0,11.0,12.0]}"#
+    );
+    roundtrip_variant(transform);
+}
+
+#[itest]
+fn serde_strings() {
+    assert_eq!(json(&GodotString::from("hello")), r#""hello""#);
+    roundtrip(GodotString::from("ünïcödé"));
+
+    let name: StringName = serde_json::from_str(r#""name""#).unwrap();
+    assert_eq!(GodotString::from(&name), GodotString::from("name"));
+
+    let path: NodePath = serde_json::from_str(r#""../parent/child:position""#).unwrap();
+    assert_eq!(json(&path), r#""../parent/child:position""#);
+}
+
+#[itest]
+fn serde_instance_id() {
+    roundtrip(InstanceId::from_nonzero(-42));
+    assert!(serde_json::from_str::<InstanceId>("0").is_err());
+}
+
+#[itest]
+fn serde_variant() {
+    let variant = GodotString::from("text").to_variant();
+    assert_eq!(json(&variant), r#"{"String":"text"}"#);
+
+    let back: Variant = serde_json::from_str(r#"{"Int":42}"#).unwrap();
+    assert_eq!(back, 42.to_variant());
+
+    let back: Variant = serde_json::from_str(&json(&Vector2::new(3.0, 4.0).to_variant())).unwrap();
+    assert_eq!(back, Vector2::new(3.0, 4.0).to_variant());
+}
+
+#[itest]
+fn serde_containers() {
+    let array: Array = engine_value(Value::Array(vec![
+        Value::Int(1),
+        Value::String("two".to_string()),
+        Value::Array(vec![Value::Bool(true)]),
+    ]));
+    assert_eq!(
+        json(&array),
+        r#"[{"Int":1},{"String":"two"},{"Array":[{"Bool":true}]}]"#
+    );
+    roundtrip_variant(array);
+
+    let dictionary: Dictionary = engine_value(Value::Dictionary(vec![
+        (Value::String("hp".to_string()), Value::Int(100)),
+        (Value::Int(7), Value::Vector2([1.0, 2.0])),
+    ]));
+    assert_eq!(
+        json(&dictionary),
+        r#"[[{"String":"hp"},{"Int":100}],[{"Int":7},{"Vector2":[1.0,2.0]}]]"#
+    );
+    roundtrip_variant(dictionary);
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+fn json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("serialize")
+}
+
+fn roundtrip<T>(value: T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let back: T = serde_json::from_str(&json(&value)).expect("deserialize");
+    assert_eq!(back, value);
+}
+
+/// For types without `PartialEq`, which are compared through Godot's variant equality instead.
+fn roundtrip_variant<T>(value: T)
+where
+    T: Serialize + DeserializeOwned + ToVariant,
+{
+    let back: T = serde_json::from_str(&json(&value)).expect("deserialize");
+    assert_eq!(back.to_variant(), value.to_variant());
+}
+
+fn engine_value<T: FromVariant>(value: Value) -> T {
+    value.to_variant().to()
+}
@@ -5,7 +5,8 @@
  */
 
 use crate::itest;
-use godot::builtin::{GodotString, StringName};
+use godot::builtin::{string_name, GodotString, StringName, ToVariant};
+use std::collections::HashSet;
 
 // TODO use tests from godot-rust/gdnative
 
@@ -15,11 +16,20 @@ pub fn run() -> bool {
     ok &= string_equality();
     ok &= string_ordering();
     ok &= string_clone();
+    ok &= string_chars();
+    ok &= string_find();
+    ok &= string_begins_ends_with();
+    ok &= string_substr();
+    ok &= string_case_and_split();
+    ok &= string_format();
+    ok &= string_utf16();
+    ok &= string_hash();
     ok &= string_name_conversion();
     ok &= string_name_default_construct();
     ok &= string_name_eq_hash();
     ok &= string_name_ord();
     ok &= string_name_clone();
+    ok &= string_name_macro();
     ok
 }
 
@@ -61,6 +71,87 @@ fn string_clone() {
     assert_eq!(first, cloned);
 }
 
+#[itest]
+fn string_chars() {
+    let string = GodotString::from("ö→x");
+    assert_eq!(string.len(), 3);
+    assert!(!string.is_empty());
+    assert!(GodotString::new().is_empty());
+
+    let chars: Vec<char> = string.chars().collect();
+    assert_eq!(chars, vec!['ö', '→', 'x']);
+    assert_eq!(string.chars().rev().next(), Some('x'));
+}
+
+#[itest]
+fn string_find() {
+    let string = GodotString::from("ab→ab");
+    assert_eq!(string.find("ab"), Some(0));
+    assert_eq!(string.rfind("ab"), Some(3));
+    assert_eq!(string.find("→a"), Some(2));
+    assert_eq!(string.find("abc"), None);
+    assert_eq!(string.rfind("long string"), None);
+}
+
+#[itest]
+fn string_begins_ends_with() {
+    let string = GodotString::from("Godot→Rust");
+    assert!(string.begins_with("Godot"));
+    assert!(string.ends_with("→Rust"));
+    assert!(!string.begins_with("Rust"));
+    assert!(!string.ends_with("Godot→Rust, longer"));
+}
+
+#[itest]
+fn string_substr() {
+    let string = GodotString::from("Godot→Rust");
+    assert_eq!(string.substr(..5), GodotString::from("Godot"));
+    assert_eq!(string.substr(5..6), GodotString::from("→"));
+    assert_eq!(string.substr(6..), GodotString::from("Rust"));
+}
+
+#[itest]
+fn string_case_and_split() {
+    let string = GodotString::from("Some Words");
+    assert_eq!(string.to_lower(), GodotString::from("some words"));
+    assert_eq!(string.to_upper(), GodotString::from("SOME WORDS"));
+
+    let parts = GodotString::from("a,b,,c").split(",").to_variant();
+    assert_eq!(parts.to_string(), r#"["a", "b", "", "c"]"#);
+}
+
+#[itest]
+fn string_format() {
+    let format = GodotString::from("value: %d");
+    let formatted = format.format(&42.to_variant());
+    assert_eq!(formatted, Some(GodotString::from("value: 42")));
+
+    let bad = GodotString::from("%d %d").format(&42.to_variant());
+    assert_eq!(bad, None);
+}
+
+#[itest]
+fn string_utf16() {
+    let string = GodotString::from("a𝄞");
+    let utf16 = string.to_utf16();
+    assert_eq!(utf16, "a𝄞".encode_utf16().collect::<Vec<_>>());
+    assert_eq!(GodotString::from_utf16(&utf16), string);
+}
+
+#[itest]
+fn string_hash() {
+    let set: HashSet<GodotString> = [
+        GodotString::from("string_1"),
+        GodotString::from("string_2"),
+        GodotString::from("string_1"),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&GodotString::from("string_2")));
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
 #[itest]
@@ -82,15 +173,46 @@ fn string_name_default_construct() {
 
 #[itest]
 fn string_name_eq_hash() {
-    // TODO
+    let name = StringName::from("some name");
+    let same = StringName::from("some name");
+    let different = StringName::from("other name");
+
+    assert_eq!(name, same);
+    assert_ne!(name, different);
+    assert_eq!(StringName::default(), StringName::from(""));
+
+    let set: HashSet<StringName> = [name, same, different].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&StringName::from("other name")));
 }
 
 #[itest]
 fn string_name_ord() {
-    // TODO
+    let low = StringName::from("Alpha");
+    let high = StringName::from("Beta");
+
+    // Order is defined by Godot; only check consistency
+    assert_eq!(low < high, high > low);
+    assert_eq!(low.cmp(&low), std::cmp::Ordering::Equal);
 }
 
 #[itest]
 fn string_name_clone() {
-    // TODO
+    let first = StringName::from("some name");
+    let cloned = first.clone();
+    drop(first);
+
+    assert_eq!(cloned, StringName::from("some name"));
+}
+
+#[itest]
+fn string_name_macro() {
+    fn get() -> &'static StringName {
+        string_name!("static name")
+    }
+
+    let first = get();
+    let second = get();
+    assert!(std::ptr::eq(first, second), "same call site yields same static");
+    assert_eq!(*first, StringName::from("static name"));
 }
@@ -0,0 +1,157 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::{expect_panic, itest};
+use godot::engine::{node, thread, Mutex, Semaphore, Thread};
+use godot::obj::{AccessError, SendGd};
+use godot::prelude::*;
+
+pub fn run() -> bool {
+    let mut ok = true;
+    ok &= thread_send_engine_object();
+    ok &= thread_send_user_object();
+    ok &= thread_send_shared_object();
+    ok &= thread_send_node_in_tree();
+    ok &= thread_try_bind_wrong_thread();
+    ok &= thread_mutex_guard();
+    ok &= thread_spawn_join();
+    ok &= thread_semaphore();
+    ok
+}
+
+#[itest]
+fn thread_send_engine_object() {
+    let obj = SendGd::new(RefCounted::new());
+
+    let back = std::thread::spawn(move || {
+        let obj = obj.into_inner();
+        assert_eq!(obj.get_reference_count(), 1);
+        SendGd::new(obj)
+    })
+    .join()
+    .expect("worker thread panicked");
+
+    let obj = back.into_inner();
+    assert_eq!(obj.get_reference_count(), 1);
+}
+
+#[itest]
+fn thread_send_user_object() {
+    let obj = SendGd::new(Gd::new(ThreadPayload { value: 1 }));
+
+    let back = std::thread::spawn(move || {
+        let mut obj = obj.into_inner();
+        obj.bind_mut().value += 1;
+        SendGd::new(obj)
+    })
+    .join()
+    .expect("worker thread panicked");
+
+    // Ownership is transferred back to this thread
+    let obj = back.into_inner();
+    assert_eq!(obj.bind().value, 2);
+}
+
+#[itest]
+fn thread_send_shared_object() {
+    let obj = RefCounted::new();
+    let shared = obj.share();
+
+    expect_panic("SendGd of object with multiple references", move || {
+        let _ = SendGd::new(shared);
+    });
+}
+
+#[itest]
+fn thread_send_node_in_tree() {
+    let mut parent = Node::new_alloc();
+    let child = Node::new_alloc();
+    parent.add_child(
+        child.share(),
+        false,
+        node::InternalMode::INTERNAL_MODE_DISABLED,
+    );
+
+    // Parent is not part of the scene tree, so neither is the child
+    let child = SendGd::new(child).into_inner();
+    assert!(!child.is_inside_tree());
+
+    parent.free();
+}
+
+#[itest]
+fn thread_try_bind_wrong_thread() {
+    // Manually managed and outside the tree, so a second pointer may stay on this thread
+    let mut node = Gd::<ThreadNode>::new_default();
+    let sent = SendGd::new(node.share());
+
+    std::thread::spawn(move || {
+        let _node = sent.into_inner();
+    })
+    .join()
+    .expect("worker thread panicked");
+
+    // The worker thread now owns the instance; accessing it here is an error, not a panic
+    assert!(matches!(
+        node.try_bind(),
+        Err(AccessError::WrongThread { .. })
+    ));
+    assert!(matches!(
+        node.try_bind_mut(),
+        Err(AccessError::WrongThread { .. })
+    ));
+
+    node.free();
+}
+
+#[itest]
+fn thread_mutex_guard() {
+    let mut mutex = Mutex::new();
+
+    {
+        let _guard = mutex.lock();
+    }
+
+    // Godot mutexes are recursive, so this succeeds on the same thread; the guard must still unlock
+    let guard = mutex.try_lock();
+    assert!(guard.is_some());
+}
+
+#[itest]
+fn thread_spawn_join() {
+    let obj = RefCounted::new();
+    let callable = Callable::from_object_method(obj.share(), "get_instance_id");
+
+    let handle =
+        Thread::spawn(callable, thread::Priority::PRIORITY_NORMAL).expect("thread started");
+    assert_eq!(handle.join(), obj.instance_id().to_variant());
+}
+
+#[itest]
+fn thread_semaphore() {
+    let mut semaphore = Semaphore::new();
+    let callable = Callable::from_object_method(semaphore.share(), "wait");
+
+    // Dropping the handle waits for the thread, which only finishes once the semaphore is posted
+    let handle =
+        Thread::spawn(callable, thread::Priority::PRIORITY_NORMAL).expect("thread started");
+    semaphore.post();
+    drop(handle);
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(GodotClass)]
+pub struct ThreadPayload {
+    value: i32,
+}
+
+#[derive(GodotClass)]
+#[class(init, base=Node)]
+pub struct ThreadNode {
+    #[base]
+    base: Base<Node>,
+}
@@ -5,6 +5,7 @@
  */
 
 use crate::itest;
+use godot::builtin::variant::encoding::Value;
 use godot::builtin::{FromVariant, GodotString, StringName, ToVariant, Variant, Vector2, Vector3};
 use godot::obj::InstanceId;
 use godot::sys::{GodotFfi, VariantOperator, VariantType};
@@ -23,6 +24,7 @@ pub fn run() -> bool {
     ok &= variant_evaluate_total_order();
     ok &= variant_sys_conversion();
     ok &= variant_sys_conversion2();
+    ok &= variant_encoding_roundtrip();
     ok
 }
 
@@ -208,6 +210,26 @@ fn variant_sys_conversion2() {
     */
 }
 
+#[itest]
+fn variant_encoding_roundtrip() {
+    let value = Value::Array(vec![
+        Value::Int(-7),
+        Value::String("some string".to_string()),
+        Value::Vector2([1.5, -2.0]),
+        Value::Dictionary(vec![(Value::StringName("key".to_string()), Value::Bool(true))]),
+    ]);
+
+    let variant = value.to_variant();
+    assert_eq!(variant.get_type(), VariantType::Array);
+
+    let back = Value::from_variant(&variant);
+    assert_eq!(back, value);
+
+    let variant = Vector2::new(1.5, -2.0).to_variant();
+    assert_eq!(Value::from_variant(&variant), Value::Vector2([1.5, -2.0]));
+    assert_eq!(Value::Vector2([1.5, -2.0]).to_variant(), variant);
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
 fn roundtrip<T>(value: T)
@@ -7,10 +7,16 @@
 #![allow(dead_code)]
 
 use godot::bind::{godot_api, GodotClass, GodotExt};
-use godot::builtin::GodotString;
-use godot::engine::RefCounted;
+use godot::builder::ClassBuilder;
+use godot::builtin::meta::{PropertyInfo, VariantMetadata};
+use godot::builtin::{GodotString, StringName, ToVariant, Variant};
+use godot::engine::global::PropertyHint;
+use godot::engine::notify::NodeNotification;
+use godot::engine::virtuals::{NodeVirtual, RefCountedVirtual};
+use godot::engine::{Node, Object, RefCounted};
 use godot::obj::{Base, Gd};
 use godot::test::itest;
+use std::collections::HashMap;
 
 /// Simple class, that deliberately has no constructor accessible from GDScript
 #[derive(GodotClass, Debug)]
@@ -39,9 +45,138 @@ impl GodotExt for VirtualMethodTest {
     }
 }
 
+#[derive(GodotClass, Debug)]
+#[class(init, base=Node)]
+struct VirtualReadyTest {
+    #[base]
+    some_base: Base<Node>,
+
+    ready_calls: i32,
+}
+
+#[godot_api]
+impl VirtualReadyTest {}
+
+#[godot_api]
+impl NodeVirtual for VirtualReadyTest {
+    fn ready(&mut self) {
+        self.ready_calls += 1;
+    }
+
+    fn to_string(&self) -> GodotString {
+        format!("VirtualReadyTest[ready_calls={}]", self.ready_calls).into()
+    }
+}
+
+#[derive(GodotClass, Debug)]
+#[class(init, base=Node)]
+struct NotificationTest {
+    #[base]
+    some_base: Base<Node>,
+
+    sequence: Vec<NodeNotification>,
+}
+
+#[godot_api]
+impl NotificationTest {}
+
+#[godot_api]
+impl NodeVirtual for NotificationTest {
+    fn on_notification(&mut self, what: NodeNotification) {
+        self.sequence.push(what);
+    }
+}
+
+/// Properties are defined by a schema at runtime
+#[derive(GodotClass, Debug)]
+#[class(init, base=RefCounted)]
+struct DynamicPropertiesTest {
+    #[base]
+    some_base: Base<RefCounted>,
+
+    values: HashMap<String, i64>,
+}
+
+#[godot_api]
+impl DynamicPropertiesTest {}
+
+#[godot_api]
+impl RefCountedVirtual for DynamicPropertiesTest {
+    fn get_property(&self, name: &StringName) -> Option<Variant> {
+        self.values
+            .get(&name.to_string())
+            .map(|value| value.to_variant())
+    }
+
+    fn set_property(&mut self, name: &StringName, value: Variant) -> bool {
+        match self.values.get_mut(&name.to_string()) {
+            Some(slot) => {
+                *slot = value.to();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn property_list(&self) -> Vec<PropertyInfo> {
+        self.values
+            .keys()
+            .map(|name| i64::property_info(name))
+            .collect()
+    }
+
+    // Every schema property reverts to 0
+    fn property_can_revert(&self, name: &StringName) -> bool {
+        self.values.contains_key(&name.to_string())
+    }
+
+    fn property_get_revert(&self, name: &StringName) -> Option<Variant> {
+        self.property_can_revert(name).then(|| 0.to_variant())
+    }
+}
+
+/// Symbols are registered through the builder instead of attributes
+#[derive(GodotClass, Debug)]
+#[class(init, base=RefCounted)]
+struct BuilderTest {
+    #[base]
+    some_base: Base<RefCounted>,
+
+    health: i64,
+}
+
+#[godot_api]
+impl BuilderTest {}
+
+#[godot_api]
+impl RefCountedVirtual for BuilderTest {
+    fn register_class(builder: &mut ClassBuilder<Self>) {
+        builder
+            .method("add", |_this: &mut BuilderTest, a: i64, b: i64| a + b)
+            .done();
+        builder
+            .property(
+                "health",
+                |this: &BuilderTest| this.health,
+                |this: &mut BuilderTest, value: i64| this.health = value,
+            )
+            .hint(PropertyHint::PROPERTY_HINT_RANGE, "0,100")
+            .done();
+        builder.signal("died", &[]);
+        builder.constant("MAX_HEALTH", 100);
+    }
+}
+
 pub(crate) fn run() -> bool {
     let mut ok = true;
     ok &= test_to_string();
+    ok &= test_virtual_trait_ready();
+    ok &= test_notification_engine();
+    ok &= test_notification_custom();
+    ok &= test_dynamic_properties();
+    ok &= test_dynamic_property_list();
+    ok &= test_dynamic_property_revert();
+    ok &= test_class_builder();
     ok
 }
 
@@ -56,3 +191,120 @@ fn test_to_string() {
     let _obj = Gd::<VirtualMethodTest>::new_default();
     dbg!(_obj);
 }
+
+#[itest]
+fn test_virtual_trait_ready() {
+    let obj = Gd::<VirtualReadyTest>::new_default();
+
+    // Node::_notification() invokes the _ready virtual on NOTIFICATION_READY
+    const NOTIFICATION_READY: i64 = 13;
+    let mut node = obj.share().upcast::<Node>();
+    node.notification(NOTIFICATION_READY, false);
+
+    assert_eq!(obj.bind().ready_calls, 1);
+    assert_eq!(format!("{node}"), "VirtualReadyTest[ready_calls=1]");
+
+    node.free();
+}
+
+#[itest]
+fn test_notification_engine() {
+    let obj = Gd::<NotificationTest>::new_default();
+
+    let mut node = obj.share().upcast::<Node>();
+    node.notify(NodeNotification::READY);
+
+    assert_eq!(obj.bind().sequence, vec![NodeNotification::READY]);
+    assert_eq!(i32::from(NodeNotification::READY), 13);
+
+    node.free();
+}
+
+#[itest]
+fn test_notification_custom() {
+    let obj = Gd::<NotificationTest>::new_default();
+
+    // Values unknown to the engine are forwarded as-is
+    let custom = NodeNotification::from(12345);
+    let mut node = obj.share().upcast::<Node>();
+    node.notify(custom);
+    node.notify_reversed(custom);
+
+    assert_eq!(obj.bind().sequence, vec![custom, custom]);
+    assert_eq!(i32::from(custom), 12345);
+
+    node.free();
+}
+
+#[itest]
+fn test_dynamic_properties() {
+    let mut obj = Gd::<DynamicPropertiesTest>::new_default();
+    obj.bind_mut().values.insert("health".to_string(), 100);
+
+    let mut object = obj.share().upcast::<Object>();
+    assert_eq!(object.get(StringName::from("health")), 100.to_variant());
+
+    object.set(StringName::from("health"), 42.to_variant());
+    assert_eq!(obj.bind().values["health"], 42);
+
+    // Unknown to the schema: falls back to regular lookup, which finds nothing
+    assert!(object.get(StringName::from("mana")).is_nil());
+}
+
+#[itest]
+fn test_dynamic_property_list() {
+    let mut obj = Gd::<DynamicPropertiesTest>::new_default();
+    obj.bind_mut().values.insert("health".to_string(), 100);
+    obj.bind_mut().values.insert("armor".to_string(), 5);
+
+    // Returned as array of dictionaries; Godot releases the list handed out by Rust after copying it
+    let mut object = obj.share().upcast::<Object>();
+    let list = object
+        .call(StringName::from("get_property_list"), &[])
+        .to_string();
+    assert!(list.contains("\"health\""), "missing property: {list}");
+    assert!(list.contains("\"armor\""), "missing property: {list}");
+    assert!(!list.contains("\"mana\""), "unexpected property: {list}");
+
+    // Repeated requests allocate and release a new list each time
+    let again = object
+        .call(StringName::from("get_property_list"), &[])
+        .to_string();
+    assert_eq!(again, list);
+}
+
+#[itest]
+fn test_dynamic_property_revert() {
+    let mut obj = Gd::<DynamicPropertiesTest>::new_default();
+    obj.bind_mut().values.insert("health".to_string(), 100);
+
+    let mut object = obj.share().upcast::<Object>();
+    let mut call = |method: &str, property: &str| {
+        object.call(
+            StringName::from(method),
+            &[StringName::from(property).to_variant()],
+        )
+    };
+
+    assert_eq!(call("property_can_revert", "health"), true.to_variant());
+    assert_eq!(call("property_get_revert", "health"), 0.to_variant());
+
+    // Without a revert value, Godot returns nil
+    assert_eq!(call("property_can_revert", "mana"), false.to_variant());
+    assert!(call("property_get_revert", "mana").is_nil());
+}
+
+#[itest]
+fn test_class_builder() {
+    let obj = Gd::<BuilderTest>::new_default();
+    let mut object = obj.share().upcast::<Object>();
+
+    let sum = object.call(StringName::from("add"), &[3.to_variant(), 4.to_variant()]);
+    assert_eq!(sum, 7.to_variant());
+
+    object.set(StringName::from("health"), 42.to_variant());
+    assert_eq!(obj.bind().health, 42);
+    assert_eq!(object.get(StringName::from("health")), 42.to_variant());
+    assert!(object.has_method(StringName::from("get_health")));
+    assert!(object.has_signal(StringName::from("died")));
+}